@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use chrono::Datelike;
+
+use crate::{Error, Money, Report};
+
+// Ordered from best to worst
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum BudgetStatus {
+    UnderBudget,
+    NearLimit,
+    OverBudget,
+}
+
+impl fmt::Display for BudgetStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BudgetStatus::UnderBudget => write!(f, "under budget"),
+            BudgetStatus::NearLimit => write!(f, "near limit"),
+            BudgetStatus::OverBudget => write!(f, "over budget"),
+        }
+    }
+}
+
+// Monthly spending limits per category. Spending at or above
+// near_limit_percent of a limit is flagged as being near it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Budget {
+    limits: HashMap<String, Money>,
+    pub near_limit_percent: i64,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget {
+            limits: HashMap::new(),
+            near_limit_percent: 90,
+        }
+    }
+}
+
+impl Budget {
+    pub fn with_limit(mut self, category: &str, monthly_limit: Money) -> Self {
+        self.limits.insert(category.to_owned(), monthly_limit);
+        self
+    }
+
+    // One "category limit" pair per line, e.g. "Gasoline 120.00"
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .try_fold(Budget::default(), |budget, (i, line)| {
+                let line_number = i + 1;
+                let trimmed = line.trim();
+                let split =
+                    trimmed
+                        .rfind(char::is_whitespace)
+                        .ok_or_else(|| Error::MissingCategory {
+                            line_number,
+                            line: line.to_owned(),
+                        })?;
+                let limit = trimmed[split..]
+                    .trim()
+                    .parse()
+                    .map_err(|_| Error::InvalidAmount {
+                        line_number,
+                        line: line.to_owned(),
+                    })?;
+
+                Ok(budget.with_limit(trimmed[..split].trim(), limit))
+            })
+    }
+
+    pub fn limit(&self, category: &str) -> Option<Money> {
+        self.limits.get(category).copied()
+    }
+
+    fn status(&self, spent: Money, limit: Money) -> BudgetStatus {
+        if spent > limit {
+            BudgetStatus::OverBudget
+        } else if spent > Money::default()
+            && spent.cents() * 100 >= limit.cents() * self.near_limit_percent
+        {
+            BudgetStatus::NearLimit
+        } else {
+            BudgetStatus::UnderBudget
+        }
+    }
+}
+
+// (year, month), or None for undated transactions
+type MonthKey = Option<(i32, u32)>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CategorySummary {
+    pub category: String,
+    pub transaction_count: usize,
+    pub total_income: Money,
+    pub total_expense: Money,
+    pub average_expense: Money,
+    pub budget: Option<Money>,
+    pub budget_status: Option<BudgetStatus>,
+    // The month the budget status comes from, as (year, month). None when
+    // that spending is undated.
+    pub budget_month: Option<(i32, u32)>,
+}
+
+impl<'a> Report<'a> {
+    // Categories come back sorted by name. Budgets are monthly, so each
+    // month's spending is checked against the limit and the worst month is
+    // flagged. Undated spending is checked together as if it were one month.
+    pub fn categories(&self, budget: &Budget) -> Vec<CategorySummary> {
+        let mut summaries: BTreeMap<&str, CategorySummary> = BTreeMap::new();
+        let mut expense_counts: HashMap<&str, usize> = HashMap::new();
+        let mut monthly_expenses: HashMap<&str, BTreeMap<MonthKey, Money>> = HashMap::new();
+        for entry in &self.entries {
+            let transaction = entry.transaction;
+            let summary =
+                summaries
+                    .entry(&transaction.category)
+                    .or_insert_with(|| CategorySummary {
+                        category: transaction.category.clone(),
+                        transaction_count: 0,
+                        total_income: Money::default(),
+                        total_expense: Money::default(),
+                        average_expense: Money::default(),
+                        budget: None,
+                        budget_status: None,
+                        budget_month: None,
+                    });
+
+            summary.transaction_count += 1;
+            if transaction.kind.is_income() {
+                summary.total_income += transaction.amount;
+            } else if transaction.kind.is_expense() {
+                summary.total_expense += transaction.amount;
+                *expense_counts.entry(&transaction.category).or_insert(0) += 1;
+                let month = transaction.date.map(|date| (date.year(), date.month()));
+                *monthly_expenses
+                    .entry(&transaction.category)
+                    .or_default()
+                    .entry(month)
+                    .or_default() += transaction.amount;
+            }
+        }
+
+        summaries
+            .into_iter()
+            .map(|(category, mut summary)| {
+                let expense_count = expense_counts.get(category).copied().unwrap_or(0);
+                summary.average_expense = Money::average(summary.total_expense, expense_count);
+                summary.budget = budget.limit(category);
+                if let Some(limit) = summary.budget {
+                    // The worst status, then the most spent, then the latest month
+                    let worst = monthly_expenses
+                        .get(category)
+                        .into_iter()
+                        .flatten()
+                        .map(|(&month, &spent)| (budget.status(spent, limit), spent, month))
+                        .max();
+                    summary.budget_status =
+                        Some(worst.map_or(BudgetStatus::UnderBudget, |(status, _, _)| status));
+                    summary.budget_month = worst.and_then(|(_, _, month)| month);
+                }
+                summary
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::*;
+
+    const INPUT: &str = "1000.00
+125 Market 60.00
+126 Gasoline 30.00
+127 Market 30.00
+deposit 128 Refund 10.00
+129 Video 7.45";
+
+    #[test]
+    fn it_summarizes_each_category() -> Result<(), Error> {
+        let ledger = Ledger::parse(INPUT)?;
+        let categories = ledger.report().categories(&Budget::default());
+
+        let names: Vec<&str> = categories.iter().map(|c| c.category.as_str()).collect();
+        assert_eq!(names, vec!["Gasoline", "Market", "Refund", "Video"]);
+
+        let market = &categories[1];
+        assert_eq!(market.transaction_count, 2);
+        assert_eq!(market.total_expense, Money::from_cents(9000));
+        assert_eq!(market.average_expense, Money::from_cents(4500));
+        assert_eq!(market.budget, None);
+        assert_eq!(market.budget_status, None);
+
+        let refund = &categories[2];
+        assert_eq!(refund.total_income, Money::from_cents(1000));
+        assert_eq!(refund.total_expense, Money::from_cents(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_flags_categories_against_their_budget() -> Result<(), Error> {
+        let ledger = Ledger::parse(INPUT)?;
+        let budget = Budget::default()
+            .with_limit("Market", Money::from_cents(8000))
+            .with_limit("Gasoline", Money::from_cents(3200))
+            .with_limit("Video", Money::from_cents(2000));
+        let statuses: Vec<Option<BudgetStatus>> = ledger
+            .report()
+            .categories(&budget)
+            .iter()
+            .map(|c| c.budget_status)
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                Some(BudgetStatus::NearLimit),
+                Some(BudgetStatus::OverBudget),
+                None,
+                Some(BudgetStatus::UnderBudget),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_checks_budgets_month_by_month() {
+        let transaction = |day: u32, month: u32, cents: i64| Transaction {
+            check_number: day.to_string(),
+            category: "Market".to_owned(),
+            amount: Money::from_cents(cents),
+            date: NaiveDate::from_ymd_opt(2019, month, day),
+            kind: TransactionKind::Check,
+        };
+        let budget = Budget::default().with_limit("Market", Money::from_cents(10000));
+
+        // June is over even though July is quiet enough to cover it
+        let ledger = Ledger::new(
+            Money::from_cents(100000),
+            vec![
+                transaction(1, 6, 6000),
+                transaction(15, 6, 6000),
+                transaction(1, 7, 1000),
+            ],
+        );
+        let categories = ledger.report().categories(&budget);
+        assert_eq!(categories[0].budget, Some(Money::from_cents(10000)));
+        assert_eq!(categories[0].budget_status, Some(BudgetStatus::OverBudget));
+        assert_eq!(categories[0].budget_month, Some((2019, 6)));
+
+        let ledger = Ledger::new(
+            Money::from_cents(100000),
+            vec![transaction(1, 6, 2000), transaction(1, 7, 9500)],
+        );
+        let categories = ledger.report().categories(&budget);
+        assert_eq!(categories[0].budget_status, Some(BudgetStatus::NearLimit));
+        assert_eq!(categories[0].budget_month, Some((2019, 7)));
+    }
+
+    #[test]
+    fn it_does_not_flag_zero_budgets_with_no_spending() -> Result<(), Error> {
+        let ledger = Ledger::parse(
+            "100.00
+deposit 1 Market 10.00",
+        )?;
+        let budget = Budget::default().with_limit("Market", Money::from_cents(0));
+        let categories = ledger.report().categories(&budget);
+
+        assert_eq!(categories[0].budget_status, Some(BudgetStatus::UnderBudget));
+        assert_eq!(categories[0].budget_month, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_budgets() {
+        assert_eq!(
+            Budget::parse("Market 200.00\n\nEating Out 75"),
+            Ok(Budget::default()
+                .with_limit("Market", Money::from_cents(20000))
+                .with_limit("Eating Out", Money::from_cents(7500)))
+        );
+        assert_eq!(
+            Budget::parse("Market lots"),
+            Err(Error::InvalidAmount {
+                line_number: 1,
+                line: "Market lots".to_owned()
+            })
+        );
+        assert_eq!(
+            Budget::parse("200.00"),
+            Err(Error::MissingCategory {
+                line_number: 1,
+                line: "200.00".to_owned()
+            })
+        );
+    }
+}
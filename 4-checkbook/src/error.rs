@@ -0,0 +1,67 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    EmptyInput,
+    MissingOpeningBalance { line_number: usize, line: String },
+    MissingCheckNumber { line_number: usize, line: String },
+    MissingCategory { line_number: usize, line: String },
+    InvalidAmount { line_number: usize, line: String },
+    InvalidDate { line_number: usize, line: String },
+    InvalidKind { line_number: usize, line: String },
+    InvalidRecord { line_number: usize, line: String },
+    MissingField { line_number: usize, field: String },
+    Io(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::EmptyInput => write!(f, "The checkbook is empty"),
+            Error::MissingOpeningBalance { line_number, line } => write!(
+                f,
+                "Line {}: could not parse an opening balance from {:?}",
+                line_number, line
+            ),
+            Error::MissingCheckNumber { line_number, line } => write!(
+                f,
+                "Line {}: missing check number in {:?}",
+                line_number, line
+            ),
+            Error::MissingCategory { line_number, line } => {
+                write!(f, "Line {}: missing category in {:?}", line_number, line)
+            }
+            Error::InvalidAmount { line_number, line } => write!(
+                f,
+                "Line {}: could not parse an amount from {:?}",
+                line_number, line
+            ),
+            Error::InvalidDate { line_number, line } => write!(
+                f,
+                "Line {}: could not parse a date from {:?}",
+                line_number, line
+            ),
+            Error::InvalidKind { line_number, line } => write!(
+                f,
+                "Line {}: unknown transaction kind in {:?}",
+                line_number, line
+            ),
+            Error::InvalidRecord { line_number, line } => {
+                write!(f, "Line {}: malformed record {:?}", line_number, line)
+            }
+            Error::MissingField { line_number, field } => {
+                write!(f, "Line {}: missing field {:?}", line_number, field)
+            }
+            Error::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error.to_string())
+    }
+}
@@ -0,0 +1,204 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{Ledger, Money, Period, Report, Transaction, TransactionKind};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Schedule {
+    Daily,
+    Weekly(Weekday),
+    // Days past the end of a short month fall on its last day, so 31 means
+    // the last day of every month
+    Monthly(u32),
+    EveryNDays { start: NaiveDate, days: u32 },
+}
+
+impl Schedule {
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        match *self {
+            Schedule::Daily => true,
+            Schedule::Weekly(weekday) => date.weekday() == weekday,
+            Schedule::Monthly(day) => {
+                let last_day =
+                    Period::month(date.year(), date.month()).map_or(31, |month| month.end.day());
+                date.day() == day.min(last_day)
+            }
+            Schedule::EveryNDays { start, days } => {
+                days > 0 && date >= start && (date - start).num_days() % i64::from(days) == 0
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Recurring {
+    pub schedule: Schedule,
+    pub check_number: String,
+    pub category: String,
+    pub amount: Money,
+    pub kind: TransactionKind,
+}
+
+impl Recurring {
+    pub fn occurrences(&self, period: Period) -> Vec<Transaction> {
+        period
+            .start
+            .iter_days()
+            .take_while(|date| *date <= period.end)
+            .filter(|date| self.schedule.occurs_on(*date))
+            .map(|date| self.transaction(date))
+            .collect()
+    }
+
+    fn transaction(&self, date: NaiveDate) -> Transaction {
+        Transaction {
+            check_number: self.check_number.clone(),
+            category: self.category.clone(),
+            amount: self.amount,
+            date: Some(date),
+            kind: self.kind,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Forecast {
+    pub period: Period,
+    pub opening_balance: Money,
+    pub transactions: Vec<Transaction>,
+    pub first_negative: Option<NaiveDate>,
+}
+
+impl Forecast {
+    pub fn report(&self) -> Report<'_> {
+        Report::new(self.opening_balance, &self.transactions)
+    }
+}
+
+impl Ledger {
+    // Projects the recurring items over the period, starting from this ledger's
+    // closing balance. Items landing on the same day keep the order they were
+    // given in.
+    pub fn forecast(&self, recurring: &[Recurring], period: Period) -> Forecast {
+        let mut transactions: Vec<Transaction> = recurring
+            .iter()
+            .flat_map(|item| item.occurrences(period))
+            .collect();
+        transactions.sort_by_key(|transaction| transaction.date);
+
+        let opening_balance = self.report().closing_balance;
+        let first_negative = if opening_balance.is_negative() {
+            Some(period.start)
+        } else {
+            Report::new(opening_balance, &transactions)
+                .entries
+                .iter()
+                .find(|entry| entry.balance.is_negative())
+                .and_then(|entry| entry.transaction.date)
+        };
+
+        Forecast {
+            period,
+            opening_balance,
+            transactions,
+            first_negative,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Weekday};
+
+    use crate::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn rent() -> Recurring {
+        Recurring {
+            schedule: Schedule::Monthly(1),
+            check_number: "0".to_owned(),
+            category: "Rent".to_owned(),
+            amount: Money::from_cents(120000),
+            kind: TransactionKind::Check,
+        }
+    }
+
+    fn paycheck() -> Recurring {
+        Recurring {
+            schedule: Schedule::EveryNDays {
+                start: date(2019, 7, 26),
+                days: 14,
+            },
+            check_number: "0".to_owned(),
+            category: "Paycheck".to_owned(),
+            amount: Money::from_cents(45000),
+            kind: TransactionKind::Deposit,
+        }
+    }
+
+    #[test]
+    fn it_schedules_recurring_items() {
+        assert!(Schedule::Monthly(31).occurs_on(date(2019, 2, 28)));
+        assert!(!Schedule::Monthly(31).occurs_on(date(2019, 3, 30)));
+        assert!(Schedule::Monthly(31).occurs_on(date(2019, 3, 31)));
+        assert!(Schedule::Weekly(Weekday::Fri).occurs_on(date(2019, 7, 26)));
+
+        let biweekly = Schedule::EveryNDays {
+            start: date(2019, 7, 5),
+            days: 14,
+        };
+        assert!(!biweekly.occurs_on(date(2019, 6, 21)));
+        assert!(biweekly.occurs_on(date(2019, 7, 19)));
+        assert!(!biweekly.occurs_on(date(2019, 7, 26)));
+
+        let dates: Vec<Option<NaiveDate>> = rent()
+            .occurrences(Period::months_from(date(2019, 7, 15), 3).unwrap())
+            .iter()
+            .map(|t| t.date)
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                Some(date(2019, 8, 1)),
+                Some(date(2019, 9, 1)),
+                Some(date(2019, 10, 1))
+            ]
+        );
+    }
+
+    #[test]
+    fn it_projects_the_balance_and_flags_the_first_negative_day() -> Result<(), Error> {
+        let ledger = Ledger::parse("1000.00\n125 Market 100.00")?;
+        let forecast = ledger.forecast(
+            &[rent(), paycheck()],
+            Period::days_from(date(2019, 7, 26), 60).unwrap(),
+        );
+
+        assert_eq!(forecast.opening_balance, Money::from_cents(90000));
+        // Paid on 7/26, rent on 8/1 leaves 150.00, and two more paychecks
+        // in August fall 150.00 short of rent on 9/1
+        assert_eq!(forecast.first_negative, Some(date(2019, 9, 1)));
+
+        let report = forecast.report();
+        assert_eq!(report.transaction_count, 7);
+        assert_eq!(report.entries[0].balance, Money::from_cents(135000));
+        assert_eq!(report.entries[1].balance, Money::from_cents(15000));
+        assert_eq!(report.entries[4].balance, Money::from_cents(-15000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_flag_a_healthy_forecast() -> Result<(), Error> {
+        let ledger = Ledger::parse("5000.00")?;
+        let forecast =
+            ledger.forecast(&[rent()], Period::months_from(date(2019, 7, 1), 3).unwrap());
+
+        assert_eq!(forecast.first_negative, None);
+        assert_eq!(forecast.report().closing_balance, Money::from_cents(140000));
+
+        Ok(())
+    }
+}
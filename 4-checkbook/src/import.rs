@@ -0,0 +1,280 @@
+use chrono::NaiveDate;
+
+use crate::{Error, Money, Transaction, TransactionKind};
+
+mod csv;
+mod json;
+mod ofx;
+mod qif;
+
+pub use self::csv::CsvImporter;
+pub use self::json::JsonImporter;
+pub use self::ofx::OfxImporter;
+pub use self::qif::QifImporter;
+
+// Bank exports rarely carry a category, so anything without one lands here
+pub const DEFAULT_CATEGORY: &str = "Uncategorized";
+
+pub trait Importer {
+    fn import(&self, input: &str) -> Result<Vec<Transaction>, Error>;
+}
+
+// Importers first work out the money leaving the account, so a debit is
+// positive and a credit is negative, and then pick a kind to match
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SignConvention {
+    NegativeIsDebit,
+    PositiveIsDebit,
+}
+
+impl SignConvention {
+    fn to_expense(self, amount: Money) -> Money {
+        match self {
+            SignConvention::NegativeIsDebit => -amount,
+            SignConvention::PositiveIsDebit => amount,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum AmountFields {
+    Single {
+        field: String,
+        sign_convention: SignConvention,
+    },
+    Split {
+        debit: String,
+        credit: String,
+    },
+}
+
+// Maps the named columns (CSV) or keys (JSON) of a record onto a Transaction.
+// Leaving check_number unmapped numbers each transaction by where its record
+// is, the line number for CSV and the record number for JSON, and leaving
+// kind unmapped picks one from the sign of the amount.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FieldMapping {
+    pub check_number: Option<String>,
+    pub category: Option<String>,
+    pub kind: Option<String>,
+    pub date: Option<String>,
+    pub date_format: String,
+    pub amount: AmountFields,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        FieldMapping {
+            check_number: Some("check_number".to_owned()),
+            category: Some("category".to_owned()),
+            kind: None,
+            date: None,
+            date_format: "%Y-%m-%d".to_owned(),
+            amount: AmountFields::Single {
+                field: "amount".to_owned(),
+                sign_convention: SignConvention::NegativeIsDebit,
+            },
+        }
+    }
+}
+
+impl FieldMapping {
+    // Every column or key the mapping reads
+    fn fields(&self) -> Vec<&str> {
+        let amount = match &self.amount {
+            AmountFields::Single { field, .. } => vec![field.as_str()],
+            AmountFields::Split { debit, credit } => vec![debit.as_str(), credit.as_str()],
+        };
+
+        [&self.check_number, &self.category, &self.kind, &self.date]
+            .iter()
+            .filter_map(|name| name.as_deref())
+            .chain(amount)
+            .collect()
+    }
+
+    fn transaction<F>(&self, line_number: usize, line: &str, field: F) -> Result<Transaction, Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let required = |name: &str| {
+            field(name).ok_or_else(|| Error::MissingField {
+                line_number,
+                field: name.to_owned(),
+            })
+        };
+        let optional = |name: &Option<String>| -> Result<Option<String>, Error> {
+            match name {
+                Some(name) => Ok(Some(required(name)?).filter(|value| !value.trim().is_empty())),
+                None => Ok(None),
+            }
+        };
+        let invalid_amount = || Error::InvalidAmount {
+            line_number,
+            line: line.to_owned(),
+        };
+
+        let mapped_check_number = optional(&self.check_number)?;
+        let is_check = mapped_check_number.is_some();
+        let check_number = mapped_check_number
+            .map(|value| value.trim().to_owned())
+            .unwrap_or_else(|| line_number.to_string());
+        let kind = match optional(&self.kind)? {
+            Some(value) => Some(value.trim().parse().map_err(|_| Error::InvalidKind {
+                line_number,
+                line: line.to_owned(),
+            })?),
+            None => None,
+        };
+        let category = optional(&self.category)?
+            .map(|value| value.trim().to_owned())
+            .unwrap_or_else(|| DEFAULT_CATEGORY.to_owned());
+        let date = match optional(&self.date)? {
+            Some(value) => Some(
+                NaiveDate::parse_from_str(value.trim(), &self.date_format).map_err(|_| {
+                    Error::InvalidDate {
+                        line_number,
+                        line: line.to_owned(),
+                    }
+                })?,
+            ),
+            None => None,
+        };
+
+        let expense = match &self.amount {
+            AmountFields::Single {
+                field,
+                sign_convention,
+            } => {
+                let amount = parse_amount(&required(field)?).ok_or_else(invalid_amount)?;
+                sign_convention.to_expense(amount)
+            }
+            AmountFields::Split { debit, credit } => {
+                let parse_column = |name: &String| -> Result<Option<Money>, Error> {
+                    let value = required(name)?;
+                    if value.trim().is_empty() {
+                        Ok(None)
+                    } else {
+                        parse_amount(&value).map(Some).ok_or_else(invalid_amount)
+                    }
+                };
+
+                match (parse_column(debit)?, parse_column(credit)?) {
+                    (None, None) => return Err(invalid_amount()),
+                    (debit, credit) => {
+                        debit.unwrap_or_default().abs() - credit.unwrap_or_default().abs()
+                    }
+                }
+            }
+        };
+
+        let (kind, amount) = kind_and_amount(kind, is_check, expense);
+
+        Ok(Transaction {
+            check_number,
+            category,
+            amount,
+            date,
+            kind,
+        })
+    }
+}
+
+// Without a kind from the export, money coming in is a deposit and money going
+// out is a check if it has a check number or a withdrawal if not. The amount
+// is flipped as needed so the balance still moves the way the bank says.
+fn kind_and_amount(
+    kind: Option<TransactionKind>,
+    is_check: bool,
+    expense: Money,
+) -> (TransactionKind, Money) {
+    let kind = kind.unwrap_or(if expense.is_negative() {
+        TransactionKind::Deposit
+    } else if is_check {
+        TransactionKind::Check
+    } else {
+        TransactionKind::Withdrawal
+    });
+    let amount = if kind.is_income() { -expense } else { expense };
+
+    (kind, amount)
+}
+
+// Bank exports like to decorate their numbers, so this accepts "$1,234.50" and
+// accounting style negatives like "(12.00)"
+fn parse_amount(raw: &str) -> Option<Money> {
+    let cleaned: String = raw
+        .trim()
+        .chars()
+        .filter(|c| *c != '$' && *c != ',' && !c.is_whitespace())
+        .collect();
+
+    if cleaned.starts_with('(') && cleaned.ends_with(')') && cleaned.len() > 2 {
+        cleaned[1..cleaned.len() - 1]
+            .parse::<Money>()
+            .ok()
+            .map(|amount| -amount)
+    } else {
+        cleaned.strip_prefix('+').unwrap_or(&cleaned).parse().ok()
+    }
+}
+
+fn line_number_at(input: &str, byte_offset: usize) -> usize {
+    input[..byte_offset].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_decorated_amounts() {
+        assert_eq!(parse_amount("$1,234.50"), Some(Money::from_cents(123450)));
+        assert_eq!(parse_amount(" -12.00 "), Some(Money::from_cents(-1200)));
+        assert_eq!(parse_amount("+3"), Some(Money::from_cents(300)));
+        assert_eq!(parse_amount("(12.00)"), Some(Money::from_cents(-1200)));
+        assert_eq!(parse_amount("twelve"), None);
+        assert_eq!(parse_amount("()"), None);
+    }
+
+    #[test]
+    fn it_picks_a_kind_from_the_sign() {
+        let out = Money::from_cents(500);
+        let incoming = Money::from_cents(-500);
+
+        assert_eq!(
+            kind_and_amount(None, true, out),
+            (TransactionKind::Check, out)
+        );
+        assert_eq!(
+            kind_and_amount(None, false, out),
+            (TransactionKind::Withdrawal, out)
+        );
+        assert_eq!(
+            kind_and_amount(None, true, incoming),
+            (TransactionKind::Deposit, out)
+        );
+        assert_eq!(
+            kind_and_amount(Some(TransactionKind::Transfer), false, incoming),
+            (TransactionKind::Transfer, incoming)
+        );
+        assert_eq!(
+            kind_and_amount(Some(TransactionKind::Fee), false, out),
+            (TransactionKind::Fee, out)
+        );
+    }
+
+    #[test]
+    fn it_applies_sign_conventions() {
+        let debit = Money::from_cents(-500);
+
+        assert_eq!(
+            SignConvention::NegativeIsDebit.to_expense(debit),
+            Money::from_cents(500)
+        );
+        assert_eq!(
+            SignConvention::PositiveIsDebit.to_expense(debit),
+            Money::from_cents(-500)
+        );
+    }
+}
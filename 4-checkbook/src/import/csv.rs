@@ -0,0 +1,234 @@
+use crate::import::{line_number_at, FieldMapping, Importer};
+use crate::{Error, Transaction};
+
+// Reads CSV with a header row, looking columns up by name through the mapping.
+// Quoted fields (including embedded delimiters, quotes and newlines) are supported.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CsvImporter {
+    pub mapping: FieldMapping,
+    pub delimiter: u8,
+}
+
+impl Default for CsvImporter {
+    fn default() -> Self {
+        CsvImporter {
+            mapping: FieldMapping::default(),
+            delimiter: b',',
+        }
+    }
+}
+
+impl Importer for CsvImporter {
+    fn import(&self, input: &str) -> Result<Vec<Transaction>, Error> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .from_reader(input.as_bytes());
+        let invalid_record = |error: ::csv::Error| {
+            let line_number = error
+                .position()
+                .map(|position| position.line() as usize)
+                .unwrap_or(1);
+
+            Error::InvalidRecord {
+                line_number,
+                line: input
+                    .lines()
+                    .nth(line_number - 1)
+                    .unwrap_or_default()
+                    .to_owned(),
+            }
+        };
+
+        let headers: Vec<String> = reader
+            .headers()
+            .map_err(invalid_record)?
+            .iter()
+            .map(|header| header.trim().to_owned())
+            .collect();
+
+        reader
+            .records()
+            .map(|record| {
+                let record = record.map_err(invalid_record)?;
+                let (line_number, line) = match record.position() {
+                    Some(position) => {
+                        let start = position.byte() as usize;
+                        let end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+                        (line_number_at(input, start), input[start..end].trim_end())
+                    }
+                    None => (0, ""),
+                };
+
+                self.mapping.transaction(line_number, line, |name| {
+                    headers
+                        .iter()
+                        .position(|header| header == name)
+                        .and_then(|i| record.get(i))
+                        .map(str::to_owned)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::import::*;
+    use crate::*;
+
+    #[test]
+    fn it_imports_with_the_default_mapping() -> Result<(), Error> {
+        let input = "check_number,category,amount
+125,Market,-125.45
+126,\"Hardware, Tools\",-34.95
+127,Refund,10.00
+";
+
+        assert_eq!(
+            CsvImporter::default().import(input)?,
+            vec![
+                Transaction {
+                    check_number: "125".to_owned(),
+                    category: "Market".to_owned(),
+                    amount: Money::from_cents(12545),
+                    date: None,
+                    kind: TransactionKind::Check,
+                },
+                Transaction {
+                    check_number: "126".to_owned(),
+                    category: "Hardware, Tools".to_owned(),
+                    amount: Money::from_cents(3495),
+                    date: None,
+                    kind: TransactionKind::Check,
+                },
+                Transaction {
+                    check_number: "127".to_owned(),
+                    category: "Refund".to_owned(),
+                    amount: Money::from_cents(1000),
+                    date: None,
+                    kind: TransactionKind::Deposit,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_with_a_custom_mapping() -> Result<(), Error> {
+        let importer = CsvImporter {
+            mapping: FieldMapping {
+                check_number: None,
+                category: Some("Description".to_owned()),
+                kind: None,
+                date: Some("Posted".to_owned()),
+                date_format: "%m/%d/%Y".to_owned(),
+                amount: AmountFields::Split {
+                    debit: "Debit".to_owned(),
+                    credit: "Credit".to_owned(),
+                },
+            },
+            delimiter: b';',
+        };
+        let input = "Posted;Description;Debit;Credit
+07/01/2019;\"Said \"\"hi\"\" to the bank\";$1,200.00;
+07/15/2019;Paycheck;;2,500.00
+";
+
+        assert_eq!(
+            importer.import(input)?,
+            vec![
+                Transaction {
+                    check_number: "2".to_owned(),
+                    category: "Said \"hi\" to the bank".to_owned(),
+                    amount: Money::from_cents(120000),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 1).unwrap()),
+                    kind: TransactionKind::Withdrawal,
+                },
+                Transaction {
+                    check_number: "3".to_owned(),
+                    category: "Paycheck".to_owned(),
+                    amount: Money::from_cents(250000),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 15).unwrap()),
+                    kind: TransactionKind::Deposit,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_a_kind_column() -> Result<(), Error> {
+        let importer = CsvImporter {
+            mapping: FieldMapping {
+                kind: Some("type".to_owned()),
+                ..FieldMapping::default()
+            },
+            ..CsvImporter::default()
+        };
+        let input = "check_number,category,type,amount
+1,Savings,Transfer,-100.00
+2,Checking,transfer,40.00
+3,Overdraft,FEE,-35.00
+";
+        let transactions = importer.import(input)?;
+
+        let kinds: Vec<TransactionKind> = transactions.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TransactionKind::Transfer,
+                TransactionKind::Transfer,
+                TransactionKind::Fee
+            ]
+        );
+        let changes: Vec<Money> = transactions.iter().map(|t| t.balance_change()).collect();
+        assert_eq!(
+            changes,
+            vec![
+                Money::from_cents(-10000),
+                Money::from_cents(4000),
+                Money::from_cents(-3500)
+            ]
+        );
+        assert_eq!(
+            importer.import("check_number,category,type,amount\n1,Savings,Gift,1.00\n"),
+            Err(Error::InvalidKind {
+                line_number: 2,
+                line: "1,Savings,Gift,1.00".to_owned()
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_bad_records() {
+        let importer = CsvImporter::default();
+
+        assert_eq!(
+            importer.import("check_number,category,amount\n125,Market,lots\n"),
+            Err(Error::InvalidAmount {
+                line_number: 2,
+                line: "125,Market,lots".to_owned()
+            })
+        );
+        assert_eq!(
+            importer.import("check_number,amount\n125,1.00\n"),
+            Err(Error::MissingField {
+                line_number: 2,
+                field: "category".to_owned()
+            })
+        );
+        assert_eq!(
+            importer.import("check_number,category,amount\n125,Market\n"),
+            Err(Error::InvalidRecord {
+                line_number: 2,
+                line: "125,Market".to_owned()
+            })
+        );
+    }
+}
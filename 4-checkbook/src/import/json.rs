@@ -0,0 +1,139 @@
+use serde_json::Value;
+
+use crate::import::{FieldMapping, Importer};
+use crate::{Error, Transaction};
+
+// Reads a JSON array of objects, looking keys up through the mapping.
+// Errors are numbered by record since JSON has no meaningful lines.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct JsonImporter {
+    pub mapping: FieldMapping,
+}
+
+impl Importer for JsonImporter {
+    fn import(&self, input: &str) -> Result<Vec<Transaction>, Error> {
+        let records: Vec<Value> =
+            serde_json::from_str(input).map_err(|e| Error::InvalidRecord {
+                line_number: e.line(),
+                line: input
+                    .lines()
+                    .nth(e.line().max(1) - 1)
+                    .unwrap_or_default()
+                    .to_owned(),
+            })?;
+
+        records
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let record_number = i + 1;
+                let line = record.to_string();
+                let object = record.as_object().ok_or_else(|| Error::InvalidRecord {
+                    line_number: record_number,
+                    line: line.clone(),
+                })?;
+
+                // A mapped key holding true or a list is there but can't be
+                // read, which isn't the same as it being missing
+                let unreadable = self.mapping.fields().into_iter().any(|name| {
+                    matches!(
+                        object.get(name),
+                        Some(Value::Bool(_)) | Some(Value::Array(_)) | Some(Value::Object(_))
+                    )
+                });
+                if unreadable {
+                    return Err(Error::InvalidRecord {
+                        line_number: record_number,
+                        line,
+                    });
+                }
+
+                self.mapping
+                    .transaction(record_number, &line, |name| match object.get(name)? {
+                        Value::String(s) => Some(s.clone()),
+                        Value::Number(n) => Some(n.to_string()),
+                        Value::Null => Some(String::new()),
+                        _ => None,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::import::*;
+    use crate::*;
+
+    #[test]
+    fn it_imports_strings_and_numbers() -> Result<(), Error> {
+        let importer = JsonImporter {
+            mapping: FieldMapping {
+                date: Some("date".to_owned()),
+                ..FieldMapping::default()
+            },
+        };
+        let input = r#"[
+            {"check_number": 125, "category": "Market", "amount": -125.45, "date": "2019-07-26"},
+            {"check_number": "126", "category": "Deposit", "amount": "1,000.00", "date": "2019-07-27"}
+        ]"#;
+
+        assert_eq!(
+            importer.import(input)?,
+            vec![
+                Transaction {
+                    check_number: "125".to_owned(),
+                    category: "Market".to_owned(),
+                    amount: Money::from_cents(12545),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 26).unwrap()),
+                    kind: TransactionKind::Check,
+                },
+                Transaction {
+                    check_number: "126".to_owned(),
+                    category: "Deposit".to_owned(),
+                    amount: Money::from_cents(100000),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 27).unwrap()),
+                    kind: TransactionKind::Deposit,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_bad_records() {
+        let importer = JsonImporter::default();
+
+        assert_eq!(
+            importer.import(r#"[{"check_number": 1, "category": "Market", "amount": true}]"#),
+            Err(Error::InvalidRecord {
+                line_number: 1,
+                line: r#"{"amount":true,"category":"Market","check_number":1}"#.to_owned()
+            })
+        );
+        assert_eq!(
+            importer.import(r#"[{"check_number": 1, "category": "Market"}]"#),
+            Err(Error::MissingField {
+                line_number: 1,
+                field: "amount".to_owned()
+            })
+        );
+        assert_eq!(
+            importer.import(r#"[{"check_number": 1, "category": "Market", "amount": 1}, 7]"#),
+            Err(Error::InvalidRecord {
+                line_number: 2,
+                line: "7".to_owned()
+            })
+        );
+        assert_eq!(
+            importer.import("[{"),
+            Err(Error::InvalidRecord {
+                line_number: 1,
+                line: "[{".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use regex::Regex;
+
+use crate::import::{kind_and_amount, line_number_at, parse_amount, Importer, DEFAULT_CATEGORY};
+use crate::{Error, Transaction, TransactionKind};
+
+// Reads the STMTTRN records out of an OFX statement. Both the SGML (OFX 1.x)
+// and XML (OFX 2.x) flavours work since leaf closing tags are ignored.
+// OFX amounts are negative for debits.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct OfxImporter;
+
+impl Importer for OfxImporter {
+    fn import(&self, input: &str) -> Result<Vec<Transaction>, Error> {
+        lazy_static! {
+            static ref TRANSACTION_REGEX: Regex =
+                Regex::new(r"(?is)<STMTTRN>(.*?)</STMTTRN>").unwrap();
+            static ref TAG_REGEX: Regex = Regex::new(r"<([A-Za-z0-9.]+)>([^<\r\n]*)").unwrap();
+        }
+
+        TRANSACTION_REGEX
+            .captures_iter(input)
+            .enumerate()
+            .map(|(i, captures)| {
+                let record = captures.get(1).unwrap();
+                let line_number = line_number_at(input, record.start());
+                let line = record.as_str().trim();
+
+                let tags: HashMap<String, &str> = TAG_REGEX
+                    .captures_iter(record.as_str())
+                    .map(|tag| {
+                        (
+                            tag[1].to_ascii_uppercase(),
+                            tag.get(2).unwrap().as_str().trim(),
+                        )
+                    })
+                    .filter(|(_, value)| !value.is_empty())
+                    .collect();
+
+                let amount = tags.get("TRNAMT").ok_or_else(|| Error::MissingField {
+                    line_number,
+                    field: "TRNAMT".to_owned(),
+                })?;
+                let amount = parse_amount(amount).ok_or_else(|| Error::InvalidAmount {
+                    line_number,
+                    line: line.to_owned(),
+                })?;
+                let date = match tags.get("DTPOSTED") {
+                    Some(date) => Some(
+                        date.get(..8)
+                            .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+                            .ok_or_else(|| Error::InvalidDate {
+                                line_number,
+                                line: line.to_owned(),
+                            })?,
+                    ),
+                    None => None,
+                };
+                let kind =
+                    tags.get("TRNTYPE")
+                        .and_then(|kind| match kind.to_ascii_uppercase().as_str() {
+                            "CHECK" => Some(TransactionKind::Check),
+                            "DEP" | "CREDIT" | "DIRECTDEP" | "INT" | "DIV" => {
+                                Some(TransactionKind::Deposit)
+                            }
+                            "FEE" | "SRVCHG" => Some(TransactionKind::Fee),
+                            "XFER" => Some(TransactionKind::Transfer),
+                            _ => None,
+                        });
+                let (kind, amount) = kind_and_amount(kind, tags.contains_key("CHECKNUM"), -amount);
+                let check_number = tags
+                    .get("CHECKNUM")
+                    .or_else(|| tags.get("FITID"))
+                    .map_or_else(|| (i + 1).to_string(), |s| (*s).to_owned());
+                let category = tags
+                    .get("NAME")
+                    .or_else(|| tags.get("MEMO"))
+                    .map_or(DEFAULT_CATEGORY, |s| *s)
+                    .to_owned();
+
+                Ok(Transaction {
+                    check_number,
+                    category,
+                    amount,
+                    date,
+                    kind,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::import::*;
+    use crate::*;
+
+    const SGML_STATEMENT: &str = "OFXHEADER:100
+DATA:OFXSGML
+
+<OFX>
+<BANKMSGSRSV1><STMTTRNRS><STMTRS>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>CHECK
+<DTPOSTED>20190726120000[-5:EST]
+<TRNAMT>-125.45
+<FITID>20190726001
+<CHECKNUM>125
+<NAME>Market
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20190727
+<TRNAMT>1500.00
+<FITID>20190727001
+<MEMO>Paycheck
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS></STMTTRNRS></BANKMSGSRSV1>
+</OFX>";
+
+    #[test]
+    fn it_imports_sgml_statements() -> Result<(), Error> {
+        assert_eq!(
+            OfxImporter.import(SGML_STATEMENT)?,
+            vec![
+                Transaction {
+                    check_number: "125".to_owned(),
+                    category: "Market".to_owned(),
+                    amount: Money::from_cents(12545),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 26).unwrap()),
+                    kind: TransactionKind::Check,
+                },
+                Transaction {
+                    check_number: "20190727001".to_owned(),
+                    category: "Paycheck".to_owned(),
+                    amount: Money::from_cents(150000),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 27).unwrap()),
+                    kind: TransactionKind::Deposit,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_xml_statements() -> Result<(), Error> {
+        let input = "<OFX><STMTTRN><TRNAMT>-7.45</TRNAMT><NAME>Video</NAME></STMTTRN></OFX>";
+
+        assert_eq!(
+            OfxImporter.import(input)?,
+            vec![Transaction {
+                check_number: "1".to_owned(),
+                category: "Video".to_owned(),
+                amount: Money::from_cents(745),
+                date: None,
+                kind: TransactionKind::Withdrawal,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_bad_records() {
+        assert_eq!(
+            OfxImporter.import("<OFX>\n<STMTTRN>\n<NAME>Video\n</STMTTRN>"),
+            Err(Error::MissingField {
+                line_number: 2,
+                field: "TRNAMT".to_owned()
+            })
+        );
+        assert_eq!(
+            OfxImporter.import("<STMTTRN><TRNAMT>-1.00<DTPOSTED>July</STMTTRN>"),
+            Err(Error::InvalidDate {
+                line_number: 1,
+                line: "<TRNAMT>-1.00<DTPOSTED>July".to_owned()
+            })
+        );
+    }
+}
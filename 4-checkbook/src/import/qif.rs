@@ -0,0 +1,204 @@
+use chrono::NaiveDate;
+
+use crate::import::{kind_and_amount, parse_amount, Importer, DEFAULT_CATEGORY};
+use crate::{Error, Transaction, TransactionKind};
+
+// Reads the records of a QIF bank export, one field per line and each record
+// ending in "^". QIF amounts are negative for debits and dates are US style,
+// with an optional apostrophe before the year ("7/26'19").
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct QifImporter;
+
+#[derive(Default)]
+struct Record<'a> {
+    line_number: usize,
+    lines: Vec<&'a str>,
+    date: Option<&'a str>,
+    amount: Option<&'a str>,
+    check_number: Option<&'a str>,
+    payee: Option<&'a str>,
+    category: Option<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    fn into_transaction(self, record_number: usize) -> Result<Transaction, Error> {
+        let line = self.lines.join("\n");
+        let line_number = self.line_number;
+
+        let amount = self.amount.ok_or_else(|| Error::MissingField {
+            line_number,
+            field: "T".to_owned(),
+        })?;
+        let amount = parse_amount(amount).ok_or_else(|| Error::InvalidAmount {
+            line_number,
+            line: line.clone(),
+        })?;
+        let date = match self.date {
+            Some(date) => Some(parse_date(date).ok_or_else(|| Error::InvalidDate {
+                line_number,
+                line: line.clone(),
+            })?),
+            None => None,
+        };
+
+        // The check number field doubles as a transaction type in most exports
+        let (kind, is_check) = match self.check_number.map(str::to_ascii_uppercase) {
+            Some(ref n) if n == "DEP" => (Some(TransactionKind::Deposit), false),
+            Some(ref n) if n == "XFER" || n == "TXFR" => (Some(TransactionKind::Transfer), false),
+            Some(ref n) => (None, n.chars().all(|c| c.is_ascii_digit())),
+            None => (None, false),
+        };
+        let (kind, amount) = kind_and_amount(kind, is_check, -amount);
+
+        Ok(Transaction {
+            check_number: self
+                .check_number
+                .map_or_else(|| record_number.to_string(), str::to_owned),
+            category: self
+                .category
+                .or(self.payee)
+                .unwrap_or(DEFAULT_CATEGORY)
+                .to_owned(),
+            amount,
+            date,
+            kind,
+        })
+    }
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    let normalized: String = date
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '\'' { '/' } else { c })
+        .collect();
+
+    ["%m/%d/%y", "%m/%d/%Y", "%m-%d-%y", "%m-%d-%Y", "%Y-%m-%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(&normalized, format).ok())
+}
+
+impl Importer for QifImporter {
+    fn import(&self, input: &str) -> Result<Vec<Transaction>, Error> {
+        let mut transactions = vec![];
+        let mut record = Record::default();
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('!') {
+                continue;
+            }
+            if record.lines.is_empty() {
+                record.line_number = i + 1;
+            }
+
+            if line.starts_with('^') {
+                let finished = std::mem::take(&mut record);
+                if !finished.lines.is_empty() {
+                    transactions.push(finished.into_transaction(transactions.len() + 1)?);
+                }
+                continue;
+            }
+
+            record.lines.push(line);
+            let mut chars = line.chars();
+            let code = chars.next();
+            let value = Some(chars.as_str().trim()).filter(|value| !value.is_empty());
+            match code {
+                Some('D') => record.date = value,
+                Some('T') | Some('U') => record.amount = value,
+                Some('N') => record.check_number = value,
+                Some('P') => record.payee = value,
+                Some('L') => record.category = value,
+                _ => {}
+            }
+        }
+
+        if !record.lines.is_empty() {
+            transactions.push(record.into_transaction(transactions.len() + 1)?);
+        }
+
+        Ok(transactions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::import::*;
+    use crate::*;
+
+    #[test]
+    fn it_imports_bank_records() -> Result<(), Error> {
+        let input = "!Type:Bank
+D07/26/2019
+T-125.45
+N125
+PCorner Market
+LGroceries
+^
+D7/27'19
+U1,500.00
+T1,500.00
+PPaycheck
+^
+";
+
+        assert_eq!(
+            QifImporter.import(input)?,
+            vec![
+                Transaction {
+                    check_number: "125".to_owned(),
+                    category: "Groceries".to_owned(),
+                    amount: Money::from_cents(12545),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 26).unwrap()),
+                    kind: TransactionKind::Check,
+                },
+                Transaction {
+                    check_number: "2".to_owned(),
+                    category: "Paycheck".to_owned(),
+                    amount: Money::from_cents(150000),
+                    date: Some(NaiveDate::from_ymd_opt(2019, 7, 27).unwrap()),
+                    kind: TransactionKind::Deposit,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_imports_a_final_record_without_a_terminator() -> Result<(), Error> {
+        assert_eq!(
+            QifImporter.import("T-7.45\nPVideo")?,
+            vec![Transaction {
+                check_number: "1".to_owned(),
+                category: "Video".to_owned(),
+                amount: Money::from_cents(745),
+                date: None,
+                kind: TransactionKind::Withdrawal,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_bad_records() {
+        assert_eq!(
+            QifImporter.import("!Type:Bank\nD07/26/2019\nPMarket\n^"),
+            Err(Error::MissingField {
+                line_number: 2,
+                field: "T".to_owned()
+            })
+        );
+        assert_eq!(
+            QifImporter.import("D13/45/2019\nT-1.00\n^"),
+            Err(Error::InvalidDate {
+                line_number: 1,
+                line: "D13/45/2019\nT-1.00".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{Error, Money, Transaction};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ledger {
+    opening_balance: Money,
+    transactions: Vec<Transaction>,
+    warnings: Vec<Error>,
+}
+
+impl Ledger {
+    pub fn new(opening_balance: Money, transactions: Vec<Transaction>) -> Self {
+        Ledger {
+            opening_balance,
+            transactions,
+            warnings: vec![],
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        Self::parse_with_mode(input, false)
+    }
+
+    // Skips any transaction lines that can't be parsed, keeping them as warnings.
+    // The opening balance is still required.
+    pub fn parse_lenient(input: &str) -> Result<Self, Error> {
+        Self::parse_with_mode(input, true)
+    }
+
+    fn parse_with_mode(input: &str, lenient: bool) -> Result<Self, Error> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let (line_number, first_line) = lines.next().ok_or(Error::EmptyInput)?;
+        let opening_balance = parse_opening_balance(line_number, first_line)?;
+
+        let mut transactions = vec![];
+        let mut warnings = vec![];
+        for (line_number, line) in lines {
+            match Transaction::from_line(line_number, line) {
+                Ok(transaction) => transactions.push(transaction),
+                Err(e) if lenient => warnings.push(e),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Ledger {
+            opening_balance,
+            transactions,
+            warnings,
+        })
+    }
+
+    pub fn opening_balance(&self) -> Money {
+        self.opening_balance
+    }
+
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    pub fn warnings(&self) -> &[Error] {
+        &self.warnings
+    }
+
+    pub fn report(&self) -> Report<'_> {
+        Report::new(self.opening_balance, &self.transactions)
+    }
+}
+
+pub(crate) fn parse_opening_balance(line_number: usize, line: &str) -> Result<Money, Error> {
+    line.trim()
+        .parse()
+        .map_err(|_| Error::MissingOpeningBalance {
+            line_number,
+            line: line.to_owned(),
+        })
+}
+
+// The running balance and totals, shared by reports and the streaming processor
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct Totals {
+    pub(crate) balance: Money,
+    pub(crate) total_income: Money,
+    pub(crate) total_expense: Money,
+    pub(crate) transaction_count: usize,
+    pub(crate) income_count: usize,
+    pub(crate) expense_count: usize,
+}
+
+impl Totals {
+    pub(crate) fn new(opening_balance: Money) -> Self {
+        Totals {
+            balance: opening_balance,
+            total_income: Money::default(),
+            total_expense: Money::default(),
+            transaction_count: 0,
+            income_count: 0,
+            expense_count: 0,
+        }
+    }
+
+    // Returns the balance after the transaction
+    pub(crate) fn add(&mut self, transaction: &Transaction) -> Money {
+        self.balance += transaction.balance_change();
+        self.transaction_count += 1;
+        if transaction.kind.is_income() {
+            self.total_income += transaction.amount;
+            self.income_count += 1;
+        } else if transaction.kind.is_expense() {
+            self.total_expense += transaction.amount;
+            self.expense_count += 1;
+        }
+        self.balance
+    }
+
+    pub(crate) fn average_expense(&self) -> Money {
+        Money::average(self.total_expense, self.expense_count)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ReportEntry<'a> {
+    pub transaction: &'a Transaction,
+    pub balance: Money,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Report<'a> {
+    pub opening_balance: Money,
+    pub entries: Vec<ReportEntry<'a>>,
+    pub closing_balance: Money,
+    pub total_income: Money,
+    pub total_expense: Money,
+    pub average_expense: Money,
+    pub transaction_count: usize,
+    pub income_count: usize,
+    pub expense_count: usize,
+}
+
+impl<'a> Report<'a> {
+    pub fn new<I>(opening_balance: Money, transactions: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Transaction>,
+    {
+        let mut totals = Totals::new(opening_balance);
+        let entries = transactions
+            .into_iter()
+            .map(|transaction| ReportEntry {
+                transaction,
+                balance: totals.add(transaction),
+            })
+            .collect();
+
+        Report {
+            opening_balance,
+            entries,
+            closing_balance: totals.balance,
+            total_income: totals.total_income,
+            total_expense: totals.total_expense,
+            average_expense: totals.average_expense(),
+            transaction_count: totals.transaction_count,
+            income_count: totals.income_count,
+            expense_count: totals.expense_count,
+        }
+    }
+
+    // The entries where the running balance dips below zero, counting each
+    // stretch of being overdrawn once
+    pub fn overdrafts(&self) -> Vec<ReportEntry<'a>> {
+        let mut previous_balance = self.opening_balance;
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let went_negative = entry.balance.is_negative() && !previous_balance.is_negative();
+                previous_balance = entry.balance;
+                went_negative
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn it_exposes_the_parsed_transactions() -> Result<(), Error> {
+        let ledger = Ledger::parse("1000.00\n125 Market 125.45\n126 Hardware;! 34.9?;")?;
+
+        assert_eq!(ledger.opening_balance(), Money::from_cents(100000));
+        assert_eq!(
+            ledger.transactions(),
+            &[
+                Transaction {
+                    check_number: "125".to_owned(),
+                    category: "Market".to_owned(),
+                    amount: Money::from_cents(12545),
+                    date: None,
+                    kind: TransactionKind::Check,
+                },
+                Transaction {
+                    check_number: "126".to_owned(),
+                    category: "Hardware".to_owned(),
+                    amount: Money::from_cents(3490),
+                    date: None,
+                    kind: TransactionKind::Check,
+                },
+            ][..]
+        );
+        assert!(ledger.warnings().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_running_balances_and_totals() -> Result<(), Error> {
+        let ledger =
+            Ledger::parse("1000.00\n125 Market 125.45\n126 Hardware 34.95\n127 Video 7.45")?;
+        let report = ledger.report();
+
+        let balances: Vec<Money> = report.entries.iter().map(|e| e.balance).collect();
+        assert_eq!(
+            balances,
+            vec![
+                Money::from_cents(87455),
+                Money::from_cents(83960),
+                Money::from_cents(83215)
+            ]
+        );
+        assert_eq!(report.closing_balance, Money::from_cents(83215));
+        assert_eq!(report.total_expense, Money::from_cents(16785));
+        assert_eq!(report.average_expense, Money::from_cents(5595));
+        assert_eq!(report.transaction_count, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_can_be_built_from_transactions() {
+        let ledger = Ledger::new(
+            Money::from_cents(5000),
+            vec![Transaction {
+                check_number: "1".to_owned(),
+                category: "Coffee".to_owned(),
+                amount: Money::from_cents(450),
+                date: None,
+                kind: TransactionKind::Check,
+            }],
+        );
+
+        assert_eq!(ledger.report().closing_balance, Money::from_cents(4550));
+    }
+
+    #[test]
+    fn it_totals_income_and_expense_separately() -> Result<(), Error> {
+        let ledger = Ledger::parse(
+            "100.00
+125 Market 20.00
+deposit 126 Paycheck 500.00
+fee 127 Bank 5.00
+transfer 128 Savings 100.00
+withdrawal 129 ATM 40.00",
+        )?;
+        let report = ledger.report();
+
+        assert_eq!(report.closing_balance, Money::from_cents(43500));
+        assert_eq!(report.total_income, Money::from_cents(50000));
+        assert_eq!(report.total_expense, Money::from_cents(6500));
+        assert_eq!(report.average_expense, Money::from_cents(2167));
+        assert_eq!(report.transaction_count, 5);
+        assert_eq!(report.income_count, 1);
+        assert_eq!(report.expense_count, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_finds_where_the_balance_goes_negative() -> Result<(), Error> {
+        let ledger = Ledger::parse(
+            "100.00
+125 Rent 150.00
+126 Market 20.00
+deposit 127 Paycheck 500.00
+128 Car 450.00",
+        )?;
+        let report = ledger.report();
+        let overdrafts: Vec<(&str, Money)> = report
+            .overdrafts()
+            .iter()
+            .map(|e| (e.transaction.check_number.as_str(), e.balance))
+            .collect();
+
+        assert_eq!(
+            overdrafts,
+            vec![
+                ("125", Money::from_cents(-5000)),
+                ("128", Money::from_cents(-2000))
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -1,75 +1,46 @@
 #[macro_use]
 extern crate lazy_static;
 
-use regex::Regex;
-
-#[derive(Debug)]
-struct Transaction {
-    check_number: String, // This is just an identifier to me. Who am I to stop your UUID check number
-    category: String,
-    amount: f32,
+mod category;
+mod error;
+mod forecast;
+pub mod import;
+mod ledger;
+mod money;
+mod period;
+mod reconcile;
+pub mod render;
+mod stream;
+mod transaction;
+
+pub use crate::category::{Budget, BudgetStatus, CategorySummary};
+pub use crate::error::Error;
+pub use crate::forecast::{Forecast, Recurring, Schedule};
+pub use crate::ledger::{Ledger, Report, ReportEntry};
+pub use crate::money::Money;
+pub use crate::period::{Period, PeriodReport};
+pub use crate::reconcile::{ClearedItem, Reconciliation};
+pub use crate::stream::{process_checkbook_stream, process_checkbook_stream_lenient};
+pub use crate::transaction::{Transaction, TransactionKind};
+
+pub fn process_checkbook_string(input: &str) -> Result<String, Error> {
+    Ok(render::text(&Ledger::parse(input)?.report()))
 }
 
-impl Transaction {
-    fn from_line(line: &str) -> Self {
-        lazy_static! {
-            static ref STRIP_REGEX: Regex = Regex::new(r"[^\w \.]").unwrap();
-            static ref PARSE_REGEX: Regex = Regex::new(r"(\d+) (.+) (\d*\.\d*)").unwrap();
-        }
-
-        let stripped_line: String = STRIP_REGEX.replace_all(line, "").to_string();
+// Skips any transaction lines that can't be parsed, handing them back as warnings
+// alongside the report. The opening balance is still required.
+pub fn process_checkbook_string_lenient(input: &str) -> Result<(String, Vec<Error>), Error> {
+    let ledger = Ledger::parse_lenient(input)?;
 
-        println!("{}", stripped_line);
-        let captures = PARSE_REGEX.captures(&stripped_line).unwrap();
-
-        Self {
-            check_number: captures.get(1).unwrap().as_str().to_owned(),
-            category: captures.get(2).unwrap().as_str().to_owned(),
-            amount: captures.get(3).unwrap().as_str().parse().unwrap(),
-        }
-    }
-
-    fn to_string(&self) -> String {
-        format!("{} {} {:.2}", self.check_number, self.category, self.amount)
-    }
-}
-
-pub fn process_checkbook_string(input: &str) -> String {
-    let mut lines = input.lines();
-    let starting_balance: f32 = lines.next().unwrap().parse().unwrap();
-
-    let transactions = lines.map(Transaction::from_line);
-
-    let mut current_balance = starting_balance;
-    let mut total_expense = 0.;
-    let mut transaction_count = 0;
-    let mut transaction_strings: Vec<String> = transactions
-        .map(|transaction| {
-            current_balance -= transaction.amount;
-            total_expense += transaction.amount;
-            transaction_count += 1;
-            format!("{} Balance {:.2}", transaction.to_string(), current_balance)
-        })
-        .collect();
-
-    let starting_balance_string = format!("Original_Balance: {:.2}", starting_balance);
-    let total_expense_string = format!("Total expense {:.2}", total_expense);
-    let avg_expense_string = format!(
-        "Average expense {:.2}",
-        total_expense / transaction_count as f32
-    );
-
-    let mut output = vec![starting_balance_string];
-    output.append(&mut transaction_strings);
-    output.push(total_expense_string);
-    output.push(avg_expense_string);
-    output.join("\n")
+    Ok((render::text(&ledger.report()), ledger.warnings().to_vec()))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    use proptest::prelude::*;
+
     const EXAMPLE_INPUT: &str = "1000.00
 125 Market 125.45
 126 Hardware 34.95
@@ -117,12 +88,160 @@ Total expense 640.90
 Average expense 53.41";
 
     #[test]
-    fn it_works_for_the_example() {
-        assert_eq!(EXAMPLE_OUTPUT, process_checkbook_string(EXAMPLE_INPUT));
+    fn it_works_for_the_example() -> Result<(), Error> {
+        assert_eq!(EXAMPLE_OUTPUT, process_checkbook_string(EXAMPLE_INPUT)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_the_challenge() -> Result<(), Error> {
+        assert_eq!(CHALLENGE_OUTPUT, process_checkbook_string(CHALLENGE_INPUT)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_errors_on_empty_input() {
+        assert_eq!(process_checkbook_string(""), Err(Error::EmptyInput));
+        assert_eq!(process_checkbook_string("  \n\n"), Err(Error::EmptyInput));
+    }
+
+    #[test]
+    fn it_errors_on_a_missing_opening_balance() {
+        assert_eq!(
+            process_checkbook_string("125 Market 125.45"),
+            Err(Error::MissingOpeningBalance {
+                line_number: 1,
+                line: "125 Market 125.45".to_owned()
+            })
+        );
     }
 
     #[test]
-    fn it_works_for_the_challenge() {
-        assert_eq!(CHALLENGE_OUTPUT, process_checkbook_string(CHALLENGE_INPUT));
+    fn it_reports_the_line_that_failed() {
+        assert_eq!(
+            process_checkbook_string("1000.00\n125 Market 125.45\nMarket 12.00"),
+            Err(Error::MissingCheckNumber {
+                line_number: 3,
+                line: "Market 12.00".to_owned()
+            })
+        );
+        assert_eq!(
+            process_checkbook_string("1000.00\n125 12.00"),
+            Err(Error::MissingCategory {
+                line_number: 2,
+                line: "125 12.00".to_owned()
+            })
+        );
+        assert_eq!(
+            process_checkbook_string("1000.00\n125 Market twelve"),
+            Err(Error::InvalidAmount {
+                line_number: 2,
+                line: "125 Market twelve".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_collects_bad_lines_as_warnings_when_lenient() -> Result<(), Error> {
+        let input = "1000.00
+125 Market 125.45
+Hardware 34.95
+127 Video 7.45
+128 Book ???";
+        let (report, warnings) = process_checkbook_string_lenient(input)?;
+
+        assert_eq!(
+            report,
+            "Original_Balance: 1000.00
+125 Market 125.45 Balance 874.55
+127 Video 7.45 Balance 867.10
+Total expense 132.90
+Average expense 66.45"
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                Error::MissingCheckNumber {
+                    line_number: 3,
+                    line: "Hardware 34.95".to_owned()
+                },
+                Error::InvalidAmount {
+                    line_number: 5,
+                    line: "128 Book ???".to_owned()
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_amounts_with_fractions_of_a_cent() {
+        assert_eq!(
+            process_checkbook_string("1000.00\n125 Market 1.005"),
+            Err(Error::InvalidAmount {
+                line_number: 2,
+                line: "125 Market 1.005".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_averages_an_empty_checkbook_to_zero() -> Result<(), Error> {
+        assert_eq!(
+            process_checkbook_string("1000.00")?,
+            "Original_Balance: 1000.00
+Total expense 0.00
+Average expense 0.00"
+        );
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn it_reconciles_thousands_of_transactions_to_the_cent(
+            starting_cents in 0i64..100_000_000,
+            amounts in prop::collection::vec(1i64..100_000, 1000..3000),
+        ) {
+            let mut input = Money::from_cents(starting_cents).to_string();
+            for (i, cents) in amounts.iter().enumerate() {
+                input.push_str(&format!("\n{} Market {}", i, Money::from_cents(*cents)));
+            }
+
+            let output = process_checkbook_string(&input).unwrap();
+            let lines: Vec<&str> = output.lines().collect();
+
+            let total: i64 = amounts.iter().sum();
+            let final_balance = format!("Balance {}", Money::from_cents(starting_cents - total));
+            prop_assert!(lines[lines.len() - 3].ends_with(&final_balance));
+            prop_assert_eq!(
+                lines[lines.len() - 2].to_owned(),
+                format!("Total expense {}", Money::from_cents(total))
+            );
+        }
+    }
+
+    #[test]
+    fn it_works_with_deposits() -> Result<(), Error> {
+        let input = "1000.00
+125 Market 125.45
+deposit 126 Paycheck 500.00
+127 Video 7.45";
+
+        assert_eq!(
+            process_checkbook_string(input)?,
+            "Original_Balance: 1000.00
+125 Market 125.45 Balance 874.55
+deposit 126 Paycheck 500.00 Balance 1374.55
+127 Video 7.45 Balance 1367.10
+Total income 500.00
+Total expense 132.90
+Average expense 66.45"
+        );
+
+        Ok(())
     }
 }
@@ -0,0 +1,264 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
+
+use checkbook::{process_checkbook_stream_lenient, render, Error, Ledger};
+
+const USAGE: &str = "Usage: checkbook [--format text|json|csv|markdown] [--strict|--lenient] [FILE]
+
+Reads the checkbook from FILE, or from stdin when FILE is missing or \"-\".";
+
+const EXIT_INVALID_CHECKBOOK: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    format: Format,
+    lenient: bool,
+    path: Option<String>,
+    help: bool,
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options {
+        format: Format::Text,
+        lenient: false,
+        path: None,
+        help: false,
+    };
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let format = if arg == "--format" || arg == "-f" {
+            Some(args.next().ok_or("--format needs a value")?)
+        } else {
+            arg.strip_prefix("--format=").map(str::to_owned)
+        };
+
+        if let Some(format) = format {
+            options.format = match format.as_str() {
+                "text" => Format::Text,
+                "json" => Format::Json,
+                "csv" => Format::Csv,
+                "markdown" | "md" => Format::Markdown,
+                _ => return Err(format!("Unknown format: {}", format)),
+            };
+        } else if arg == "--strict" {
+            options.lenient = false;
+        } else if arg == "--lenient" {
+            options.lenient = true;
+        } else if arg == "--help" || arg == "-h" {
+            options.help = true;
+        } else if arg.starts_with('-') && arg != "-" {
+            return Err(format!("Unknown option: {}", arg));
+        } else if options.path.is_some() {
+            return Err("Only one checkbook file can be given".to_owned());
+        } else if arg != "-" {
+            options.path = Some(arg);
+        }
+    }
+
+    Ok(options)
+}
+
+fn report<R: BufRead, W: Write>(
+    options: &Options,
+    mut input: R,
+    mut output: W,
+) -> Result<Vec<Error>, Error> {
+    // Lenient text can be written as the input is read, since bad lines are
+    // skipped. Anything else needs the whole ledger first, so a strict run
+    // writes nothing when the checkbook is invalid.
+    if options.format == Format::Text && options.lenient {
+        return process_checkbook_stream_lenient(input, output);
+    }
+
+    let mut contents = String::new();
+    input.read_to_string(&mut contents)?;
+    let ledger = if options.lenient {
+        Ledger::parse_lenient(&contents)?
+    } else {
+        Ledger::parse(&contents)?
+    };
+
+    let report = ledger.report();
+    let rendered = match options.format {
+        Format::Text => format!("{}\n", render::text(&report)),
+        Format::Json => format!("{}\n", render::json(&report)),
+        Format::Csv => render::csv(&report)?,
+        Format::Markdown => format!("{}\n", render::markdown(&report)),
+    };
+    output.write_all(rendered.as_bytes())?;
+    output.flush()?;
+
+    Ok(ledger.warnings().to_vec())
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(EXIT_USAGE);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let stdout = io::stdout();
+    let result = match &options.path {
+        Some(path) => match File::open(path) {
+            Ok(file) => report(&options, BufReader::new(file), stdout.lock()),
+            Err(e) => {
+                eprintln!("Could not open {}: {}", path, e);
+                process::exit(EXIT_INVALID_CHECKBOOK);
+            }
+        },
+        None => report(&options, io::stdin().lock(), stdout.lock()),
+    };
+
+    match result {
+        Ok(warnings) => {
+            for warning in warnings {
+                eprintln!("Skipped {}", warning);
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(EXIT_INVALID_CHECKBOOK);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    #[test]
+    fn it_defaults_to_strict_text_from_stdin() {
+        assert_eq!(
+            parse_args(args(&[])),
+            Ok(Options {
+                format: Format::Text,
+                lenient: false,
+                path: None,
+                help: false,
+            })
+        );
+        assert_eq!(parse_args(args(&["-"])).map(|o| o.path), Ok(None));
+    }
+
+    #[test]
+    fn it_parses_formats_modes_and_files() {
+        assert_eq!(
+            parse_args(args(&["--format", "json", "--lenient", "ledger.txt"])),
+            Ok(Options {
+                format: Format::Json,
+                lenient: true,
+                path: Some("ledger.txt".to_owned()),
+                help: false,
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["--lenient", "--strict", "--format=markdown"])),
+            Ok(Options {
+                format: Format::Markdown,
+                lenient: false,
+                path: None,
+                help: false,
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_bad_arguments() {
+        assert_eq!(
+            parse_args(args(&["--format", "xml"])),
+            Err("Unknown format: xml".to_owned())
+        );
+        assert_eq!(
+            parse_args(args(&["--format"])),
+            Err("--format needs a value".to_owned())
+        );
+        assert_eq!(
+            parse_args(args(&["--verbose"])),
+            Err("Unknown option: --verbose".to_owned())
+        );
+        assert_eq!(
+            parse_args(args(&["a.txt", "b.txt"])),
+            Err("Only one checkbook file can be given".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_reports_in_each_format() -> Result<(), Error> {
+        let input = "100.00\n125 Market 25.00\nMarket 1.00";
+        let run = |format| -> Result<(String, Vec<Error>), Error> {
+            let options = Options {
+                format,
+                lenient: true,
+                path: None,
+                help: false,
+            };
+            let mut output = vec![];
+            let warnings = report(&options, input.as_bytes(), &mut output)?;
+            Ok((String::from_utf8(output).unwrap(), warnings))
+        };
+
+        let (text, warnings) = run(Format::Text)?;
+        assert!(text.starts_with("Original_Balance: 100.00\n125 Market 25.00 Balance 75.00\n"));
+        assert_eq!(warnings.len(), 1);
+        assert!(run(Format::Json)?
+            .0
+            .contains("\"closing_balance\":\"75.00\""));
+        assert!(run(Format::Csv)?
+            .0
+            .ends_with(",check,125,Market,25.00,75.00\n"));
+        assert!(run(Format::Markdown)?
+            .0
+            .contains("| 125 | Market | 25.00 | 75.00 |"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fails_on_bad_lines_when_strict() {
+        for &format in &[Format::Text, Format::Json] {
+            let options = Options {
+                format,
+                lenient: false,
+                path: None,
+                help: false,
+            };
+            let mut output = vec![];
+
+            assert_eq!(
+                report(
+                    &options,
+                    "100.00\n125 Market 25.00\nMarket 1.00".as_bytes(),
+                    &mut output
+                ),
+                Err(Error::MissingCheckNumber {
+                    line_number: 3,
+                    line: "Market 1.00".to_owned()
+                })
+            );
+            assert!(output.is_empty());
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// Money is stored as a whole number of cents so that long ledgers never drift
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Money(i64);
+
+#[derive(Debug, PartialEq)]
+pub struct ParseMoneyError;
+
+impl Money {
+    pub fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn abs(self) -> Self {
+        Money(self.0.abs())
+    }
+
+    // Rounds half a cent away from zero, so 39.654 becomes 39.65 and 53.4083 becomes 53.41.
+    // An empty list averages to zero.
+    pub fn average(total: Money, count: usize) -> Money {
+        if count == 0 {
+            return Money(0);
+        }
+
+        let count = count as i64;
+        let quotient = total.0 / count;
+        let remainder = total.0 % count;
+
+        if remainder.abs() * 2 >= count {
+            Money(quotient + total.0.signum())
+        } else {
+            Money(quotient)
+        }
+    }
+}
+
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (dollars, cents) = match unsigned.find('.') {
+            Some(i) => (&unsigned[..i], &unsigned[i + 1..]),
+            None => (unsigned, ""),
+        };
+
+        let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (dollars.is_empty() && cents.is_empty())
+            || !all_digits(dollars)
+            || !all_digits(cents)
+            || cents.len() > 2
+        {
+            return Err(ParseMoneyError);
+        }
+
+        let dollars: i64 = if dollars.is_empty() {
+            0
+        } else {
+            dollars.parse().map_err(|_| ParseMoneyError)?
+        };
+        let cents: i64 = format!("{:0<2}", cents)
+            .parse()
+            .map_err(|_| ParseMoneyError)?;
+
+        let total = dollars
+            .checked_mul(100)
+            .and_then(|d| d.checked_add(cents))
+            .ok_or(ParseMoneyError)?;
+
+        Ok(Money(if negative { -total } else { total }))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+
+        write!(f, "{}{}.{:02}", sign, cents / 100, cents % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money(0), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    #[test]
+    fn it_parses_amounts_into_cents() {
+        assert_eq!("24.8".parse(), Ok(Money::from_cents(2480)));
+        assert_eq!("19.00".parse(), Ok(Money::from_cents(1900)));
+        assert_eq!("128".parse(), Ok(Money::from_cents(12800)));
+        assert_eq!(".5".parse(), Ok(Money::from_cents(50)));
+        assert_eq!("-3.07".parse(), Ok(Money::from_cents(-307)));
+    }
+
+    #[test]
+    fn it_does_not_parse_invalid_amounts() {
+        assert_eq!("".parse::<Money>(), Err(ParseMoneyError));
+        assert_eq!(".".parse::<Money>(), Err(ParseMoneyError));
+        assert_eq!("1.005".parse::<Money>(), Err(ParseMoneyError));
+        assert_eq!("1.2.3".parse::<Money>(), Err(ParseMoneyError));
+        assert_eq!("twelve".parse::<Money>(), Err(ParseMoneyError));
+        assert_eq!("--1".parse::<Money>(), Err(ParseMoneyError));
+    }
+
+    #[test]
+    fn it_displays_with_two_decimal_places() {
+        assert_eq!(Money::from_cents(80173).to_string(), "801.73");
+        assert_eq!(Money::from_cents(5).to_string(), "0.05");
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(Money::from_cents(-12345).to_string(), "-123.45");
+    }
+
+    #[test]
+    fn it_rounds_the_average_half_away_from_zero() {
+        assert_eq!(
+            Money::average(Money::from_cents(19827), 5),
+            Money::from_cents(3965)
+        );
+        assert_eq!(
+            Money::average(Money::from_cents(64090), 12),
+            Money::from_cents(5341)
+        );
+        assert_eq!(
+            Money::average(Money::from_cents(5), 2),
+            Money::from_cents(3)
+        );
+        assert_eq!(
+            Money::average(Money::from_cents(-5), 2),
+            Money::from_cents(-3)
+        );
+        assert_eq!(
+            Money::average(Money::from_cents(4), 3),
+            Money::from_cents(1)
+        );
+        assert_eq!(
+            Money::average(Money::from_cents(100), 0),
+            Money::from_cents(0)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn it_round_trips_through_strings(cents in -1_000_000_000_000i64..1_000_000_000_000) {
+            let money = Money::from_cents(cents);
+            prop_assert_eq!(money.to_string().parse(), Ok(money));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+use crate::{Ledger, Money, Report, Transaction};
+
+// An inclusive range of dates
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Period {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Period {
+    // None if the period would end before it starts
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Option<Self> {
+        Some(Period { start, end }).filter(|_| start <= end)
+    }
+
+    pub fn month(year: i32, month: u32) -> Option<Self> {
+        let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let next_month = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)?
+        };
+
+        Period::new(start, next_month.pred_opt()?)
+    }
+
+    // The n days starting at start, or None for zero days
+    pub fn days_from(start: NaiveDate, days: u32) -> Option<Self> {
+        let end = start.checked_add_signed(Duration::days(i64::from(days) - 1))?;
+        Period::new(start, end)
+    }
+
+    // The n months starting at start, or None for zero months
+    pub fn months_from(start: NaiveDate, months: u32) -> Option<Self> {
+        let end = start.checked_add_months(Months::new(months))?.pred_opt()?;
+        Period::new(start, end)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} to {}",
+            self.start.format("%Y-%m-%d"),
+            self.end.format("%Y-%m-%d")
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PeriodReport<'a> {
+    pub period: Period,
+    pub report: Report<'a>,
+}
+
+impl Ledger {
+    // One report per calendar month from the first dated transaction to the
+    // last, including any quiet months in between
+    pub fn monthly_reports(&self) -> Vec<PeriodReport<'_>> {
+        let mut dates = self.transactions().iter().filter_map(|t| t.date);
+        let first = match dates.next() {
+            Some(date) => date,
+            None => return vec![],
+        };
+        let (first, last) = dates.fold((first, first), |(first, last), date| {
+            (first.min(date), last.max(date))
+        });
+
+        let mut periods = vec![];
+        let (mut year, mut month) = (first.year(), first.month());
+        while (year, month) <= (last.year(), last.month()) {
+            periods.extend(Period::month(year, month));
+            if month == 12 {
+                year += 1;
+                month = 1;
+            } else {
+                month += 1;
+            }
+        }
+
+        self.period_reports(&periods)
+    }
+
+    // Each period opens with the balance left by every transaction dated before
+    // it, so consecutive periods carry their balances forward. Transactions are
+    // ordered by date within a period, and undated transactions are left out.
+    pub fn period_reports(&self, periods: &[Period]) -> Vec<PeriodReport<'_>> {
+        let mut dated: Vec<(NaiveDate, &Transaction)> = self
+            .transactions()
+            .iter()
+            .filter_map(|t| t.date.map(|date| (date, t)))
+            .collect();
+        dated.sort_by_key(|(date, _)| *date);
+
+        periods
+            .iter()
+            .map(|period| {
+                let opening_balance = self.opening_balance()
+                    + dated
+                        .iter()
+                        .take_while(|(date, _)| *date < period.start)
+                        .map(|(_, t)| t.balance_change())
+                        .sum::<Money>();
+                let transactions = dated
+                    .iter()
+                    .filter(|(date, _)| period.contains(*date))
+                    .map(|(_, t)| *t);
+
+                PeriodReport {
+                    period: *period,
+                    report: Report::new(opening_balance, transactions),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::*;
+
+    const INPUT: &str = "1000.00
+2019-07-26 125 Market 125.45
+2019-06-03 126 Hardware 34.95
+2019-06-20 deposit 127 Paycheck 500.00
+2019-09-01 128 Book 14.32";
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn it_builds_calendar_months() {
+        assert_eq!(
+            Period::month(2019, 2),
+            Period::new(date(2019, 2, 1), date(2019, 2, 28))
+        );
+        assert_eq!(
+            Period::month(2019, 12),
+            Period::new(date(2019, 12, 1), date(2019, 12, 31))
+        );
+        assert_eq!(Period::month(2019, 13), None);
+        assert_eq!(Period::new(date(2019, 7, 2), date(2019, 7, 1)), None);
+    }
+
+    #[test]
+    fn it_builds_periods_from_a_start() {
+        assert_eq!(
+            Period::days_from(date(2019, 7, 30), 3),
+            Period::new(date(2019, 7, 30), date(2019, 8, 1))
+        );
+        assert_eq!(
+            Period::months_from(date(2019, 7, 15), 2),
+            Period::new(date(2019, 7, 15), date(2019, 9, 14))
+        );
+        assert_eq!(Period::days_from(date(2019, 7, 30), 0), None);
+        assert_eq!(Period::months_from(date(2019, 7, 30), 0), None);
+    }
+
+    #[test]
+    fn it_carries_balances_forward_month_to_month() -> Result<(), Error> {
+        let ledger = Ledger::parse(INPUT)?;
+        let reports = ledger.monthly_reports();
+
+        let summary: Vec<(String, Money, Money, usize)> = reports
+            .iter()
+            .map(|r| {
+                (
+                    r.period.to_string(),
+                    r.report.opening_balance,
+                    r.report.closing_balance,
+                    r.report.transaction_count,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "2019-06-01 to 2019-06-30".to_owned(),
+                    Money::from_cents(100000),
+                    Money::from_cents(146505),
+                    2
+                ),
+                (
+                    "2019-07-01 to 2019-07-31".to_owned(),
+                    Money::from_cents(146505),
+                    Money::from_cents(133960),
+                    1
+                ),
+                (
+                    "2019-08-01 to 2019-08-31".to_owned(),
+                    Money::from_cents(133960),
+                    Money::from_cents(133960),
+                    0
+                ),
+                (
+                    "2019-09-01 to 2019-09-30".to_owned(),
+                    Money::from_cents(133960),
+                    Money::from_cents(132528),
+                    1
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_custom_ranges() -> Result<(), Error> {
+        let ledger = Ledger::parse(INPUT)?;
+        let reports =
+            ledger.period_reports(&[Period::new(date(2019, 6, 15), date(2019, 7, 31)).unwrap()]);
+        let report = &reports[0].report;
+
+        assert_eq!(report.opening_balance, Money::from_cents(96505));
+        let categories: Vec<&str> = report
+            .entries
+            .iter()
+            .map(|e| e.transaction.category.as_str())
+            .collect();
+        assert_eq!(categories, vec!["Paycheck", "Market"]);
+        assert_eq!(report.closing_balance, Money::from_cents(133960));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_has_no_months_without_dates() -> Result<(), Error> {
+        let ledger = Ledger::parse("1000.00\n125 Market 125.45")?;
+
+        assert!(ledger.monthly_reports().is_empty());
+
+        Ok(())
+    }
+}
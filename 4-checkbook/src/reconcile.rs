@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use crate::{Ledger, Money, ReportEntry, Transaction};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ClearedItem<'a> {
+    pub ledger: &'a Transaction,
+    pub statement: &'a Transaction,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Reconciliation<'a> {
+    pub cleared: Vec<ClearedItem<'a>>,
+    pub outstanding: Vec<&'a Transaction>,
+    pub unexpected: Vec<&'a Transaction>,
+    pub ledger_duplicates: Vec<String>,
+    pub statement_duplicates: Vec<String>,
+    pub ledger_balance: Money,
+    pub statement_balance: Money,
+    pub difference: Money,
+    pub overdrafts: Vec<ReportEntry<'a>>,
+}
+
+impl<'a> Reconciliation<'a> {
+    pub fn is_balanced(&self) -> bool {
+        self.difference == Money::default()
+    }
+}
+
+fn duplicate_check_numbers(transactions: &[Transaction]) -> Vec<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for transaction in transactions {
+        *counts.entry(&transaction.check_number).or_insert(0) += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(check_number, _)| check_number.to_owned())
+        .collect()
+}
+
+impl Ledger {
+    // Statement entries clear the first unmatched ledger entry with the same
+    // check number that moves the balance by the same amount. Comparing the
+    // balance change rather than the kind lets a bank "withdrawal" clear our "check".
+    pub fn reconcile<'a>(&'a self, statement: &'a Ledger) -> Reconciliation<'a> {
+        let mut matched = vec![false; self.transactions().len()];
+        let mut cleared = vec![];
+        let mut unexpected = vec![];
+
+        for statement_transaction in statement.transactions() {
+            let position =
+                self.transactions()
+                    .iter()
+                    .enumerate()
+                    .position(|(i, ledger_transaction)| {
+                        !matched[i]
+                            && ledger_transaction.check_number == statement_transaction.check_number
+                            && ledger_transaction.balance_change()
+                                == statement_transaction.balance_change()
+                    });
+
+            match position {
+                Some(i) => {
+                    matched[i] = true;
+                    cleared.push(ClearedItem {
+                        ledger: &self.transactions()[i],
+                        statement: statement_transaction,
+                    });
+                }
+                None => unexpected.push(statement_transaction),
+            }
+        }
+
+        let outstanding = self
+            .transactions()
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(transaction, _)| transaction)
+            .collect();
+
+        let report = self.report();
+        let ledger_balance = report.closing_balance;
+        let statement_balance = statement.report().closing_balance;
+
+        Reconciliation {
+            cleared,
+            outstanding,
+            unexpected,
+            ledger_duplicates: duplicate_check_numbers(self.transactions()),
+            statement_duplicates: duplicate_check_numbers(statement.transactions()),
+            ledger_balance,
+            statement_balance,
+            difference: ledger_balance - statement_balance,
+            overdrafts: report.overdrafts(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const LEDGER: &str = "1000.00
+125 Market 125.45
+126 Hardware 34.95
+126 Hardware 34.95
+127 Video 7.45
+deposit 128 Paycheck 500.00";
+
+    const STATEMENT: &str = "1000.00
+withdrawal 125 Corner Market 125.45
+126 Hardware 34.95
+deposit 128 Payroll 500.00
+fee 900 Service charge 2.00
+127 Video 7.54";
+
+    #[test]
+    fn it_matches_by_check_number_and_amount() -> Result<(), Error> {
+        let ledger = Ledger::parse(LEDGER)?;
+        let statement = Ledger::parse(STATEMENT)?;
+        let reconciliation = ledger.reconcile(&statement);
+
+        let cleared: Vec<(&str, &str)> = reconciliation
+            .cleared
+            .iter()
+            .map(|c| (c.ledger.category.as_str(), c.statement.category.as_str()))
+            .collect();
+        assert_eq!(
+            cleared,
+            vec![
+                ("Market", "Corner Market"),
+                ("Hardware", "Hardware"),
+                ("Paycheck", "Payroll")
+            ]
+        );
+
+        let outstanding: Vec<String> = reconciliation
+            .outstanding
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        assert_eq!(outstanding, vec!["126 Hardware 34.95", "127 Video 7.45"]);
+
+        let unexpected: Vec<String> = reconciliation
+            .unexpected
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        assert_eq!(
+            unexpected,
+            vec!["fee 900 Service charge 2.00", "127 Video 7.54"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_duplicates_and_the_difference() -> Result<(), Error> {
+        let ledger = Ledger::parse(LEDGER)?;
+        let statement = Ledger::parse(STATEMENT)?;
+        let reconciliation = ledger.reconcile(&statement);
+
+        assert_eq!(reconciliation.ledger_duplicates, vec!["126"]);
+        assert!(reconciliation.statement_duplicates.is_empty());
+        assert_eq!(reconciliation.ledger_balance, Money::from_cents(129720));
+        assert_eq!(reconciliation.statement_balance, Money::from_cents(133006));
+        assert_eq!(reconciliation.difference, Money::from_cents(-3286));
+        assert!(!reconciliation.is_balanced());
+        assert!(reconciliation.overdrafts.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_balances_against_a_matching_statement() -> Result<(), Error> {
+        let ledger = Ledger::parse("10.00\n125 Market 25.00")?;
+        let reconciliation = ledger.reconcile(&ledger);
+
+        assert!(reconciliation.is_balanced());
+        assert!(reconciliation.outstanding.is_empty());
+        assert!(reconciliation.unexpected.is_empty());
+        assert_eq!(reconciliation.overdrafts.len(), 1);
+        assert_eq!(
+            reconciliation.overdrafts[0].balance,
+            Money::from_cents(-1500)
+        );
+
+        Ok(())
+    }
+}
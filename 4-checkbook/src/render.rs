@@ -0,0 +1,406 @@
+use serde_json::json;
+
+use crate::{CategorySummary, Error, Money, PeriodReport, Reconciliation, Report, Transaction};
+
+pub fn text(report: &Report) -> String {
+    let mut output = vec![opening_line(report.opening_balance)];
+    output.extend(
+        report
+            .entries
+            .iter()
+            .map(|entry| entry_line(entry.transaction, entry.balance)),
+    );
+    output.extend(totals_lines(
+        report.income_count,
+        report.total_income,
+        report.total_expense,
+        report.average_expense,
+    ));
+    output.join("\n")
+}
+
+pub(crate) fn opening_line(opening_balance: Money) -> String {
+    format!("Original_Balance: {}", opening_balance)
+}
+
+pub(crate) fn entry_line(transaction: &Transaction, balance: Money) -> String {
+    format!("{} Balance {}", transaction, balance)
+}
+
+pub(crate) fn totals_lines(
+    income_count: usize,
+    total_income: Money,
+    total_expense: Money,
+    average_expense: Money,
+) -> Vec<String> {
+    let mut output = vec![];
+    // Only ledgers that record income get the extra line, so plain checkbooks
+    // keep the original format
+    if income_count > 0 {
+        output.push(format!("Total income {}", total_income));
+    }
+    output.push(format!("Total expense {}", total_expense));
+    output.push(format!("Average expense {}", average_expense));
+    output
+}
+
+// Money is written as strings so no precision is lost to floats
+pub fn json(report: &Report) -> String {
+    let transactions: Vec<_> = report
+        .entries
+        .iter()
+        .map(|entry| {
+            let transaction = entry.transaction;
+            json!({
+                "date": transaction.date.map(|date| date.format("%Y-%m-%d").to_string()),
+                "kind": transaction.kind.to_string(),
+                "check_number": transaction.check_number,
+                "category": transaction.category,
+                "amount": transaction.amount.to_string(),
+                "balance": entry.balance.to_string(),
+            })
+        })
+        .collect();
+
+    json!({
+        "opening_balance": report.opening_balance.to_string(),
+        "transactions": transactions,
+        "closing_balance": report.closing_balance.to_string(),
+        "total_income": report.total_income.to_string(),
+        "total_expense": report.total_expense.to_string(),
+        "average_expense": report.average_expense.to_string(),
+        "transaction_count": report.transaction_count,
+    })
+    .to_string()
+}
+
+// One row per transaction, since totals don't fit the columns
+pub fn csv(report: &Report) -> Result<String, Error> {
+    let mut writer = ::csv::Writer::from_writer(vec![]);
+    let csv_error = |e: ::csv::Error| Error::Io(e.to_string());
+
+    writer
+        .write_record([
+            "date",
+            "kind",
+            "check_number",
+            "category",
+            "amount",
+            "balance",
+        ])
+        .map_err(csv_error)?;
+    for entry in &report.entries {
+        let transaction = entry.transaction;
+        writer
+            .write_record([
+                transaction
+                    .date
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                transaction.kind.to_string(),
+                transaction.check_number.clone(),
+                transaction.category.clone(),
+                transaction.amount.to_string(),
+                entry.balance.to_string(),
+            ])
+            .map_err(csv_error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| Error::Io(e.error().to_string()))?;
+    Ok(String::from_utf8(bytes).expect("csv output is built from strings"))
+}
+
+pub fn markdown(report: &Report) -> String {
+    let escape = |s: &str| s.replace('|', "\\|");
+    let mut output = vec![
+        format!("**Original balance:** {}", report.opening_balance),
+        String::new(),
+        "| Date | Kind | Check | Category | Amount | Balance |".to_owned(),
+        "| --- | --- | --- | --- | ---: | ---: |".to_owned(),
+    ];
+    output.extend(report.entries.iter().map(|entry| {
+        let transaction = entry.transaction;
+        format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            transaction
+                .date
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            transaction.kind,
+            escape(&transaction.check_number),
+            escape(&transaction.category),
+            transaction.amount,
+            entry.balance
+        )
+    }));
+    output.push(String::new());
+    output.push(format!("**Closing balance:** {}", report.closing_balance));
+    if report.income_count > 0 {
+        output.push(format!("**Total income:** {}", report.total_income));
+    }
+    output.push(format!("**Total expense:** {}", report.total_expense));
+    output.push(format!("**Average expense:** {}", report.average_expense));
+    output.join("\n")
+}
+
+pub fn statements(reports: &[PeriodReport]) -> String {
+    reports
+        .iter()
+        .map(|statement| {
+            format!(
+                "Statement {}\n{}\nClosing_Balance: {}",
+                statement.period,
+                text(&statement.report),
+                statement.report.closing_balance
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+pub fn categories(summaries: &[CategorySummary]) -> String {
+    summaries
+        .iter()
+        .map(|summary| {
+            let mut line = format!(
+                "Category {} count {} expense {} average {}",
+                summary.category,
+                summary.transaction_count,
+                summary.total_expense,
+                summary.average_expense
+            );
+            if summary.total_income != Money::default() {
+                line.push_str(&format!(" income {}", summary.total_income));
+            }
+            if let (Some(budget), Some(status)) = (summary.budget, summary.budget_status) {
+                line.push_str(&format!(" budget {} {}", budget, status));
+                if let Some((year, month)) = summary.budget_month {
+                    line.push_str(&format!(" in {}-{:02}", year, month));
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn reconciliation(reconciliation: &Reconciliation) -> String {
+    let mut output = vec![];
+    output.extend(
+        reconciliation
+            .cleared
+            .iter()
+            .map(|item| format!("Cleared {}", item.ledger)),
+    );
+    output.extend(
+        reconciliation
+            .outstanding
+            .iter()
+            .map(|transaction| format!("Outstanding {}", transaction)),
+    );
+    output.extend(
+        reconciliation
+            .unexpected
+            .iter()
+            .map(|transaction| format!("Unexpected {}", transaction)),
+    );
+    output.extend(
+        reconciliation
+            .ledger_duplicates
+            .iter()
+            .map(|check_number| format!("Duplicate check number in ledger {}", check_number)),
+    );
+    output.extend(
+        reconciliation
+            .statement_duplicates
+            .iter()
+            .map(|check_number| format!("Duplicate check number in statement {}", check_number)),
+    );
+    output.extend(
+        reconciliation
+            .overdrafts
+            .iter()
+            .map(|entry| format!("Overdraft {} Balance {}", entry.transaction, entry.balance)),
+    );
+    output.push(format!("Ledger balance {}", reconciliation.ledger_balance));
+    output.push(format!(
+        "Statement balance {}",
+        reconciliation.statement_balance
+    ));
+    output.push(format!("Difference {}", reconciliation.difference));
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const INPUT: &str = "100.00
+2019-07-26 125 Market 25.00
+deposit 126 Paycheck 50.00";
+
+    #[test]
+    fn it_renders_json() -> Result<(), Error> {
+        let ledger = Ledger::parse(INPUT)?;
+        let json: serde_json::Value =
+            serde_json::from_str(&render::json(&ledger.report())).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "opening_balance": "100.00",
+                "transactions": [
+                    {
+                        "date": "2019-07-26",
+                        "kind": "check",
+                        "check_number": "125",
+                        "category": "Market",
+                        "amount": "25.00",
+                        "balance": "75.00"
+                    },
+                    {
+                        "date": null,
+                        "kind": "deposit",
+                        "check_number": "126",
+                        "category": "Paycheck",
+                        "amount": "50.00",
+                        "balance": "125.00"
+                    }
+                ],
+                "closing_balance": "125.00",
+                "total_income": "50.00",
+                "total_expense": "25.00",
+                "average_expense": "25.00",
+                "transaction_count": 2
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_csv() -> Result<(), Error> {
+        let ledger = Ledger::new(
+            Money::from_cents(10000),
+            vec![Transaction {
+                check_number: "125".to_owned(),
+                category: "Market, Deli".to_owned(),
+                amount: Money::from_cents(2500),
+                date: None,
+                kind: TransactionKind::Check,
+            }],
+        );
+
+        assert_eq!(
+            render::csv(&ledger.report())?,
+            "date,kind,check_number,category,amount,balance
+,check,125,\"Market, Deli\",25.00,75.00
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_markdown() -> Result<(), Error> {
+        let ledger = Ledger::new(
+            Money::from_cents(10000),
+            vec![Transaction {
+                check_number: "125".to_owned(),
+                category: "Market | Deli".to_owned(),
+                amount: Money::from_cents(2500),
+                date: None,
+                kind: TransactionKind::Check,
+            }],
+        );
+
+        assert_eq!(
+            render::markdown(&ledger.report()),
+            "**Original balance:** 100.00
+
+| Date | Kind | Check | Category | Amount | Balance |
+| --- | --- | --- | --- | ---: | ---: |
+|  | check | 125 | Market \\| Deli | 25.00 | 75.00 |
+
+**Closing balance:** 75.00
+**Total expense:** 25.00
+**Average expense:** 25.00"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_categories() -> Result<(), Error> {
+        let ledger = Ledger::parse(
+            "1000.00
+125 Market 60.00
+126 Gasoline 30.00
+127 Market 30.00
+deposit 128 Refund 10.00",
+        )?;
+        let budget = Budget::default()
+            .with_limit("Market", Money::from_cents(8000))
+            .with_limit("Gasoline", Money::from_cents(5000));
+
+        assert_eq!(
+            render::categories(&ledger.report().categories(&budget)),
+            "Category Gasoline count 1 expense 30.00 average 30.00 budget 50.00 under budget
+Category Market count 2 expense 90.00 average 45.00 budget 80.00 over budget
+Category Refund count 1 expense 0.00 average 0.00 income 10.00"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_statements() -> Result<(), Error> {
+        let ledger = Ledger::parse(
+            "1000.00
+2019-06-03 126 Hardware 34.95
+2019-07-26 125 Market 125.45",
+        )?;
+
+        assert_eq!(
+            render::statements(&ledger.monthly_reports()),
+            "Statement 2019-06-01 to 2019-06-30
+Original_Balance: 1000.00
+2019-06-03 126 Hardware 34.95 Balance 965.05
+Total expense 34.95
+Average expense 34.95
+Closing_Balance: 965.05
+
+Statement 2019-07-01 to 2019-07-31
+Original_Balance: 965.05
+2019-07-26 125 Market 125.45 Balance 839.60
+Total expense 125.45
+Average expense 125.45
+Closing_Balance: 839.60"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_renders_a_reconciliation() -> Result<(), Error> {
+        let ledger = Ledger::parse("100.00\n125 Market 25.00\n126 Rent 80.00\n126 Rent 80.00")?;
+        let statement = Ledger::parse("100.00\n125 Market 25.00\nfee 900 Bank 1.00")?;
+
+        assert_eq!(
+            render::reconciliation(&ledger.reconcile(&statement)),
+            "Cleared 125 Market 25.00
+Outstanding 126 Rent 80.00
+Outstanding 126 Rent 80.00
+Unexpected fee 900 Bank 1.00
+Duplicate check number in ledger 126
+Overdraft 126 Rent 80.00 Balance -5.00
+Ledger balance -85.00
+Statement balance 74.00
+Difference -159.00"
+        );
+
+        Ok(())
+    }
+}
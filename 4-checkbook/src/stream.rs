@@ -0,0 +1,207 @@
+use std::io::{BufRead, Write};
+
+use crate::ledger::{parse_opening_balance, Totals};
+use crate::{render, Error, Transaction};
+
+// Writes each report line as soon as its transaction is read, so only the
+// running totals are kept in memory however long the input is
+pub fn process_checkbook_stream<R: BufRead, W: Write>(input: R, output: W) -> Result<(), Error> {
+    stream(input, output, Err)
+}
+
+// Like process_checkbook_stream, but skips lines that can't be parsed and
+// hands them back once the report has been written
+pub fn process_checkbook_stream_lenient<R: BufRead, W: Write>(
+    input: R,
+    output: W,
+) -> Result<Vec<Error>, Error> {
+    let mut warnings = vec![];
+    stream(input, output, |e| {
+        warnings.push(e);
+        Ok(())
+    })?;
+
+    Ok(warnings)
+}
+
+fn stream<R, W, F>(input: R, mut output: W, mut on_bad_line: F) -> Result<(), Error>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(Error) -> Result<(), Error>,
+{
+    let mut totals = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        let line_number = i + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match totals {
+            None => {
+                let opening_balance = parse_opening_balance(line_number, &line)?;
+                writeln!(output, "{}", render::opening_line(opening_balance))?;
+                totals = Some(Totals::new(opening_balance));
+            }
+            Some(ref mut totals) => match Transaction::from_line(line_number, &line) {
+                Ok(transaction) => {
+                    let balance = totals.add(&transaction);
+                    writeln!(output, "{}", render::entry_line(&transaction, balance))?;
+                }
+                Err(e) => on_bad_line(e)?,
+            },
+        }
+    }
+
+    let totals = totals.ok_or(Error::EmptyInput)?;
+    for line in render::totals_lines(
+        totals.income_count,
+        totals.total_income,
+        totals.total_expense,
+        totals.average_expense(),
+    ) {
+        writeln!(output, "{}", line)?;
+    }
+    output.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, Read};
+
+    use crate::*;
+
+    const INPUT: &str = "1233.00
+125 Hardware;! 24.8?;
+deposit 123 Paycheck 93.5
+
+127 Meat 120.90
+120 Picture 34.00";
+
+    #[test]
+    fn it_matches_the_string_output() -> Result<(), Error> {
+        let mut output = vec![];
+        process_checkbook_stream(INPUT.as_bytes(), &mut output)?;
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!("{}\n", process_checkbook_string(INPUT)?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_stops_at_the_first_bad_line() {
+        let mut output = vec![];
+        let result =
+            process_checkbook_stream("10.00\n1 Market 1.00\nMarket 2.00".as_bytes(), &mut output);
+
+        assert_eq!(
+            result,
+            Err(Error::MissingCheckNumber {
+                line_number: 3,
+                line: "Market 2.00".to_owned()
+            })
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Original_Balance: 10.00\n1 Market 1.00 Balance 9.00\n"
+        );
+    }
+
+    #[test]
+    fn it_collects_bad_lines_when_lenient() -> Result<(), Error> {
+        let mut output = vec![];
+        let warnings = process_checkbook_stream_lenient(
+            "10.00\nMarket 2.00\n1 Market 1.00".as_bytes(),
+            &mut output,
+        )?;
+
+        assert_eq!(
+            warnings,
+            vec![Error::MissingCheckNumber {
+                line_number: 2,
+                line: "Market 2.00".to_owned()
+            }]
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Original_Balance: 10.00
+1 Market 1.00 Balance 9.00
+Total expense 1.00
+Average expense 1.00
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_errors_on_empty_input() {
+        assert_eq!(
+            process_checkbook_stream("\n\n".as_bytes(), io::sink()),
+            Err(Error::EmptyInput)
+        );
+    }
+
+    // Generates a ledger line by line so the input never exists as one string
+    struct GeneratedLedger {
+        lines_left: usize,
+        pending: Vec<u8>,
+    }
+
+    impl Read for GeneratedLedger {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                if self.lines_left == 0 {
+                    return Ok(0);
+                }
+                self.lines_left -= 1;
+                self.pending = format!("{} Market 1.01\n", self.lines_left).into_bytes();
+            }
+
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    struct LastLine(Vec<u8>);
+
+    impl io::Write for LastLine {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0.ends_with(b"\n") {
+                self.0.clear();
+            }
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_streams_large_ledgers() -> Result<(), Error> {
+        let input = GeneratedLedger {
+            lines_left: 200_000,
+            pending: b"0.00\n".to_vec(),
+        };
+        let mut last_line = LastLine(vec![]);
+        process_checkbook_stream(BufReader::new(input), &mut last_line)?;
+
+        assert_eq!(
+            String::from_utf8(last_line.0).unwrap(),
+            "Average expense 1.01\n"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use chrono::NaiveDate;
+use regex::Regex;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Money};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TransactionKind {
+    Check,
+    Deposit,
+    Withdrawal,
+    Fee,
+    Transfer,
+}
+
+impl TransactionKind {
+    // Transfers move money between your own accounts, so they change the
+    // balance without counting as either income or expense
+    pub fn is_income(self) -> bool {
+        self == TransactionKind::Deposit
+    }
+
+    pub fn is_expense(self) -> bool {
+        match self {
+            TransactionKind::Check | TransactionKind::Withdrawal | TransactionKind::Fee => true,
+            TransactionKind::Deposit | TransactionKind::Transfer => false,
+        }
+    }
+
+    fn to_str(self) -> &'static str {
+        match self {
+            TransactionKind::Check => "check",
+            TransactionKind::Deposit => "deposit",
+            TransactionKind::Withdrawal => "withdrawal",
+            TransactionKind::Fee => "fee",
+            TransactionKind::Transfer => "transfer",
+        }
+    }
+}
+
+impl FromStr for TransactionKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "check" => Ok(TransactionKind::Check),
+            "deposit" => Ok(TransactionKind::Deposit),
+            "withdrawal" => Ok(TransactionKind::Withdrawal),
+            "fee" => Ok(TransactionKind::Fee),
+            "transfer" => Ok(TransactionKind::Transfer),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for TransactionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Transaction {
+    pub check_number: String, // This is just an identifier to me. Who am I to stop your UUID check number
+    pub category: String,
+    pub amount: Money,
+    pub date: Option<NaiveDate>,
+    pub kind: TransactionKind,
+}
+
+impl Transaction {
+    // Deposits add to the balance and everything else takes away from it.
+    // A negative amount reverses that, e.g. a transfer coming in.
+    pub fn balance_change(&self) -> Money {
+        if self.kind.is_income() {
+            self.amount
+        } else {
+            -self.amount
+        }
+    }
+
+    // Lines are "[date] [kind] number category amount". Dates are YYYY-MM-DD
+    // and without a kind the transaction is a check.
+    pub(crate) fn from_line(line_number: usize, line: &str) -> Result<Self, Error> {
+        lazy_static! {
+            static ref STRIP_REGEX: Regex = Regex::new(r"[^\w \.]").unwrap();
+            static ref CHECK_NUMBER_REGEX: Regex = Regex::new(r"^\d+$").unwrap();
+            static ref DATE_REGEX: Regex =
+                Regex::new(r"^\s*(\d{4}[-/]\d{1,2}[-/]\d{1,2})\s").unwrap();
+        }
+
+        // The date has to come off before stripping, which would eat its dashes
+        let (date, rest) = match DATE_REGEX.captures(line) {
+            Some(captures) => {
+                let date = captures.get(1).unwrap().as_str().replace('/', "-");
+                let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|_| {
+                    Error::InvalidDate {
+                        line_number,
+                        line: line.to_owned(),
+                    }
+                })?;
+                (Some(date), &line[captures.get(0).unwrap().end()..])
+            }
+            None => (None, line),
+        };
+
+        let stripped_line: String = STRIP_REGEX.replace_all(rest, "").to_string();
+        let mut words: Vec<&str> = stripped_line.split_whitespace().collect();
+
+        let kind = match words.first().map(|word| word.parse()) {
+            Some(Ok(kind)) => {
+                words.remove(0);
+                kind
+            }
+            _ => TransactionKind::Check,
+        };
+
+        let check_number = match words.first() {
+            Some(word) if CHECK_NUMBER_REGEX.is_match(word) => word,
+            _ => {
+                return Err(Error::MissingCheckNumber {
+                    line_number,
+                    line: line.to_owned(),
+                })
+            }
+        };
+        let amount: Money = words
+            .get(1..)
+            .and_then(|rest| rest.last())
+            .and_then(|word| word.parse().ok())
+            .ok_or_else(|| Error::InvalidAmount {
+                line_number,
+                line: line.to_owned(),
+            })?;
+        // Stripping also eats the amount's minus sign, so it's put back
+        let negative = rest
+            .split_whitespace()
+            .last()
+            .is_some_and(|word| word.starts_with('-'));
+        let amount = if negative { -amount } else { amount };
+        if words.len() < 3 {
+            return Err(Error::MissingCategory {
+                line_number,
+                line: line.to_owned(),
+            });
+        }
+
+        Ok(Self {
+            check_number: (*check_number).to_owned(),
+            category: words[1..words.len() - 1].join(" "),
+            amount,
+            date,
+            kind,
+        })
+    }
+}
+
+impl fmt::Display for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(date) = self.date {
+            write!(f, "{} ", date.format("%Y-%m-%d"))?;
+        }
+        if self.kind != TransactionKind::Check {
+            write!(f, "{} ", self.kind)?;
+        }
+        write!(f, "{} {} {}", self.check_number, self.category, self.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::*;
+
+    #[test]
+    fn it_parses_an_optional_kind() -> Result<(), Error> {
+        let deposit = Transaction::from_line(2, "Deposit 130 Paycheck 1500.00")?;
+        assert_eq!(deposit.kind, TransactionKind::Deposit);
+        assert_eq!(deposit.check_number, "130");
+        assert_eq!(deposit.category, "Paycheck");
+        assert_eq!(deposit.to_string(), "deposit 130 Paycheck 1500.00");
+
+        let check = Transaction::from_line(3, "131 Deposit box 12.00")?;
+        assert_eq!(check.kind, TransactionKind::Check);
+        assert_eq!(check.category, "Deposit box");
+        assert_eq!(check.to_string(), "131 Deposit box 12.00");
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_uses_the_kind_for_the_sign_of_the_balance_change() -> Result<(), Error> {
+        let change = |line| Transaction::from_line(1, line).map(|t| t.balance_change());
+
+        assert_eq!(change("1 Rent 10.00")?, Money::from_cents(-1000));
+        assert_eq!(change("withdrawal 1 ATM 10.00")?, Money::from_cents(-1000));
+        assert_eq!(change("fee 1 Overdraft 10.00")?, Money::from_cents(-1000));
+        assert_eq!(
+            change("transfer 1 Savings 10.00")?,
+            Money::from_cents(-1000)
+        );
+        assert_eq!(change("deposit 1 Paycheck 10.00")?, Money::from_cents(1000));
+        assert_eq!(
+            change("transfer 1 Savings -10.00")?,
+            Money::from_cents(1000)
+        );
+        assert_eq!(
+            Transaction::from_line(1, "1 Rent -$10.00")?.to_string(),
+            "1 Rent -10.00"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_parses_an_optional_date() -> Result<(), Error> {
+        let dated = Transaction::from_line(1, "2019-07-26 deposit 130 Paycheck 1500.00")?;
+        assert_eq!(dated.date, NaiveDate::from_ymd_opt(2019, 7, 26));
+        assert_eq!(dated.kind, TransactionKind::Deposit);
+        assert_eq!(dated.to_string(), "2019-07-26 deposit 130 Paycheck 1500.00");
+
+        let slashes = Transaction::from_line(1, "2019/7/4 125 Fireworks;! 24.8?;")?;
+        assert_eq!(slashes.date, NaiveDate::from_ymd_opt(2019, 7, 4));
+        assert_eq!(slashes.to_string(), "2019-07-04 125 Fireworks 24.80");
+
+        assert_eq!(Transaction::from_line(1, "125 Market 1.00")?.date, None);
+        assert_eq!(
+            Transaction::from_line(4, "2019-13-45 125 Market 1.00"),
+            Err(Error::InvalidDate {
+                line_number: 4,
+                line: "2019-13-45 125 Market 1.00".to_owned()
+            })
+        );
+
+        Ok(())
+    }
+}
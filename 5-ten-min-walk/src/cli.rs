@@ -0,0 +1,188 @@
+use std::fmt;
+use std::io;
+use std::io::prelude::*;
+
+use crate::{chosen_direction_with, Direction, Movement, SessionState, WalkMap, WalkSession};
+
+#[derive(Debug)]
+pub enum CliError {
+    Io(io::Error),
+    InvalidWalkTime(&'static str),
+    // The input ran out before the walk was over
+    EndOfInput,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Io(e) => write!(f, "{}", e),
+            CliError::InvalidWalkTime(e) => write!(f, "{}", e),
+            CliError::EndOfInput => write!(f, "The walk was abandoned before it was over"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::Io(e)
+    }
+}
+
+pub fn parse_time_of_walk(user_input: &str) -> Result<u32, &'static str> {
+    parse_time_of_walk_with(user_input, Movement::Compass)
+}
+
+pub fn parse_time_of_walk_with(user_input: &str, movement: Movement) -> Result<u32, &'static str> {
+    let parsed_input_result: Result<u32, _> = user_input.parse();
+    match parsed_input_result {
+        Ok(i) => {
+            if movement.allows_walk_of(i) {
+                Ok(i)
+            } else if movement == Movement::Compass {
+                Err("Walk time must be an even number or we will not be able to get back to where we started")
+            } else {
+                Err("Walk time must be more than a minute or we will not be able to get back to where we started")
+            }
+        }
+        Err(_) => Err("Could not parse the walk time"),
+    }
+}
+
+fn directions_to_string(path: &[Direction]) -> String {
+    path.iter()
+        .map(Direction::to_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn next_line<R: BufRead>(input: &mut R) -> Result<String, CliError> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(CliError::EndOfInput);
+    }
+
+    Ok(line.trim().to_owned())
+}
+
+pub fn run<R: BufRead, W: Write>(input: R, output: W) -> Result<SessionState, CliError> {
+    run_with(input, output, Movement::Compass)
+}
+
+// Asks for the length of the walk and then each direction in turn, until the
+// walker is home or can no longer make it back
+pub fn run_with<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    movement: Movement,
+) -> Result<SessionState, CliError> {
+    writeln!(output, "Hey I heard you want to take a walk!")?;
+    writeln!(output, "How long (in minutes) should we walk for?")?;
+    let minutes = parse_time_of_walk_with(&next_line(&mut input)?, movement)
+        .map_err(CliError::InvalidWalkTime)?;
+
+    walk(
+        input,
+        output,
+        WalkSession::with_movement(movement, minutes),
+        movement,
+    )
+}
+
+// Like run, but only walking the map's streets
+pub fn run_on_map<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    map: WalkMap,
+) -> Result<SessionState, CliError> {
+    writeln!(output, "Hey I heard you want to take a walk!")?;
+    writeln!(output, "How long (in minutes) should we walk for?")?;
+    let minutes = next_line(&mut input)?
+        .parse()
+        .map_err(|_| CliError::InvalidWalkTime("Could not parse the walk time"))?;
+
+    let session = WalkSession::on_map(map, minutes);
+    if session.state() == SessionState::Stranded {
+        return Err(CliError::InvalidWalkTime(
+            "There is no walk round the map that gets back to where we started in that time",
+        ));
+    }
+
+    walk(input, output, session, Movement::Compass)
+}
+
+fn walk<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    mut session: WalkSession,
+    movement: Movement,
+) -> Result<SessionState, CliError> {
+    while let (SessionState::Walking, Some(options)) = (session.state(), session.options()) {
+        writeln!(output, "\nYou have {} minutes left", session.minutes_left())?;
+        writeln!(output, "Which direction would you like to go?")?;
+        writeln!(
+            output,
+            "So far you'r path has been: {}",
+            directions_to_string(session.path())
+        )?;
+        writeln!(
+            output,
+            "Your current options are: {}",
+            directions_to_string(options)
+        )?;
+
+        // Directions the movement doesn't have are ignored like any other
+        // unrecognised input
+        let mut chosen_direction_option = None;
+        while chosen_direction_option.is_none() {
+            chosen_direction_option = chosen_direction_with(&next_line(&mut input)?, movement);
+        }
+
+        let chosen_direction = chosen_direction_option.unwrap();
+        writeln!(output, "You chose: {}", chosen_direction)?;
+        // On a map there may be no street that way, so ask again
+        if let Err(e) = session.step(chosen_direction) {
+            writeln!(output, "{}", e)?;
+        }
+    }
+
+    match session.state() {
+        SessionState::Home => writeln!(
+            output,
+            "YAY we made it! Here is the path we took: {}",
+            directions_to_string(session.path())
+        )?,
+        _ => {
+            writeln!(
+                output,
+                "OH NO! We won't be able to make it back in time! Try another path."
+            )?;
+            writeln!(
+                output,
+                "Here is what you tried this time: {}",
+                directions_to_string(session.path())
+            )?;
+        }
+    }
+
+    Ok(session.state())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn parse_time_of_walk_test() {
+        assert_eq!(
+            parse_time_of_walk("3"),
+            Err("Walk time must be an even number or we will not be able to get back to where we started")
+        );
+        assert_eq!(
+            parse_time_of_walk("five"),
+            Err("Could not parse the walk time")
+        );
+        assert_eq!(parse_time_of_walk("4"), Ok(4));
+    }
+}
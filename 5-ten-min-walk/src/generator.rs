@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use crate::{Direction, Movement};
+
+// SplitMix64, so a seed picks the same walk on every platform and release
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Draws past the last whole multiple of n are thrown away, so every
+    // value below n is as likely as any other
+    fn below(&mut self, n: u128) -> u128 {
+        let limit = u128::MAX - u128::MAX % n;
+        loop {
+            let value = (u128::from(self.next()) << 64) | u128::from(self.next());
+            if value < limit {
+                return value % n;
+            }
+        }
+    }
+}
+
+// The number of ways home from each position in exactly so many minutes,
+// or None once that no longer fits in a u128. The counts are built up from
+// no minutes left, one minute at a time, and only for the positions a walk
+// of the full length can reach.
+struct WalkCounter {
+    ways: Vec<HashMap<(i32, i32), Option<u128>>>,
+}
+
+impl WalkCounter {
+    fn new(movement: Movement, minutes: u32) -> Self {
+        let mut ways: Vec<HashMap<(i32, i32), Option<u128>>> = vec![];
+
+        for minutes_left in 0..=minutes {
+            let reach = minutes_left.min(minutes - minutes_left) as i32;
+            let mut counts = HashMap::new();
+            for x in -reach..=reach {
+                for y in -reach..=reach {
+                    let position = (x, y);
+                    if !movement.can_get_home(position, minutes_left) {
+                        continue;
+                    }
+
+                    let count = match ways.last() {
+                        None => Some(1),
+                        Some(previous) => movement
+                            .options_availible(position, minutes_left)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter_map(|direction| movement.step_from(position, direction))
+                            .try_fold(0u128, |total, next| {
+                                total.checked_add(previous.get(&next).copied().flatten()?)
+                            }),
+                    };
+                    counts.insert(position, count);
+                }
+            }
+
+            // Going there and back first makes a walk two minutes longer, so
+            // once a walk has too many ways home so does every longer one
+            let too_many = (minutes - minutes_left).is_multiple_of(2)
+                && counts.get(&(0, 0)).copied() == Some(None);
+            ways.push(counts);
+            if too_many {
+                break;
+            }
+        }
+
+        WalkCounter { ways }
+    }
+
+    fn ways_home(&self, position: (i32, i32), minutes_left: u32) -> Option<u128> {
+        let counts = self.ways.get(minutes_left as usize)?;
+        counts.get(&position).copied().unwrap_or(Some(0))
+    }
+}
+
+// How many walks of exactly this many minutes end back where they started,
+// or None if there are too many to count
+pub fn count_walks(minutes: u32) -> Option<u128> {
+    count_walks_with(minutes, Movement::Compass)
+}
+
+pub fn count_walks_with(minutes: u32, movement: Movement) -> Option<u128> {
+    if !movement.allows_walk_of(minutes) {
+        return Some(0);
+    }
+    WalkCounter::new(movement, minutes).ways_home((0, 0), minutes)
+}
+
+// Every walk is equally likely to be picked
+pub fn random_walk(minutes: u32, seed: u64) -> Result<Vec<Direction>, &'static str> {
+    random_walk_with(minutes, seed, Movement::Compass)
+}
+
+pub fn random_walk_with(
+    minutes: u32,
+    seed: u64,
+    movement: Movement,
+) -> Result<Vec<Direction>, &'static str> {
+    if !movement.allows_walk_of(minutes) {
+        return Err("There is no walk of that length that gets back to where we started");
+    }
+    let counter = WalkCounter::new(movement, minutes);
+    let mut random = Random(seed);
+    let mut position = (0, 0);
+    let mut path = vec![];
+
+    if counter.ways_home(position, minutes).is_none() {
+        return Err("There are too many walks of that length to pick from");
+    }
+
+    for minutes_left in (1..=minutes).rev() {
+        let options: Vec<(Direction, (i32, i32))> = movement
+            .options_availible(position, minutes_left)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|direction| Some((direction, movement.step_from(position, direction)?)))
+            .collect();
+        let weights: Vec<u128> = options
+            .iter()
+            .map(|&(_, next)| counter.ways_home(next, minutes_left - 1).unwrap_or(0))
+            .collect();
+
+        let mut choice = random.below(weights.iter().sum());
+        let ((direction, next), _) = options
+            .into_iter()
+            .zip(weights)
+            .find(|&(_, weight)| {
+                let found = choice < weight;
+                choice = choice.saturating_sub(weight);
+                found
+            })
+            .unwrap();
+
+        position = next;
+        path.push(direction);
+    }
+
+    Ok(path)
+}
+
+// Walks the paths depth first, keeping only the options at each step of
+// the current path, so even long walks take little memory
+pub struct Walks {
+    minutes: u32,
+    movement: Movement,
+    positions: Vec<(i32, i32)>,
+    path: Vec<Direction>,
+    options: Vec<(Vec<Direction>, usize)>,
+    finished: bool,
+}
+
+pub fn walks(minutes: u32) -> Walks {
+    walks_with(minutes, Movement::Compass)
+}
+
+pub fn walks_with(minutes: u32, movement: Movement) -> Walks {
+    Walks {
+        minutes,
+        movement,
+        positions: vec![(0, 0)],
+        path: vec![],
+        options: vec![(
+            movement
+                .options_availible((0, 0), minutes)
+                .unwrap_or_default(),
+            0,
+        )],
+        finished: false,
+    }
+}
+
+impl Iterator for Walks {
+    type Item = Vec<Direction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.minutes == 0 && !self.finished {
+            self.finished = true;
+            return Some(vec![]);
+        }
+
+        while let Some((options, next)) = self.options.last_mut() {
+            let direction = match options.get(*next) {
+                Some(&direction) => direction,
+                None => {
+                    self.options.pop();
+                    self.path.pop();
+                    self.positions.pop();
+                    continue;
+                }
+            };
+            *next += 1;
+
+            let movement = self.movement;
+            let position = match self
+                .positions
+                .last()
+                .and_then(|&position| movement.step_from(position, direction))
+            {
+                Some(position) => position,
+                None => continue,
+            };
+            let minutes_left = self.minutes - self.path.len() as u32 - 1;
+            if minutes_left == 0 {
+                let mut walk = self.path.clone();
+                walk.push(direction);
+                return Some(walk);
+            }
+
+            let options = self
+                .movement
+                .options_availible(position, minutes_left)
+                .unwrap_or_default();
+            self.positions.push(position);
+            self.path.push(direction);
+            self.options.push((options, 0));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn ends_at_home(walk: &[Direction]) -> bool {
+        displacement_with(walk, Movement::Compass) == Some((0, 0))
+    }
+
+    #[test]
+    fn it_counts_walks() {
+        assert_eq!(count_walks(0), Some(1));
+        assert_eq!(count_walks(2), Some(4));
+        assert_eq!(count_walks(4), Some(36));
+        assert_eq!(count_walks(3), Some(0));
+        // The number of ways for 10 minutes is 252 squared
+        assert_eq!(count_walks(10), Some(63504));
+        assert_eq!(count_walks(70), None);
+        assert_eq!(count_walks(100_000), None);
+        assert_eq!(count_walks(100_001), Some(0));
+        assert_eq!(count_walks_with(100_000, Movement::Hex), None);
+    }
+
+    #[test]
+    fn it_enumerates_every_walk() {
+        let all: Vec<Vec<Direction>> = walks(4).collect();
+        assert_eq!(all.len(), 36);
+        assert!(all.iter().all(|walk| walk.len() == 4 && ends_at_home(walk)));
+        assert!(all.contains(&vec![
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West
+        ]));
+
+        assert_eq!(walks(0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(walks(5).next(), None);
+        assert_eq!(walks(10).count(), 63504);
+        assert_eq!(walks(1000).take(3).count(), 3);
+    }
+
+    #[test]
+    fn it_picks_random_walks() {
+        let walk = random_walk(10, 42).unwrap();
+        assert_eq!(walk.len(), 10);
+        assert!(ends_at_home(&walk));
+        assert_eq!(random_walk(10, 42), Ok(walk));
+
+        let different = (0..20).filter_map(|seed| random_walk(10, seed).ok());
+        assert!(different
+            .collect::<Vec<_>>()
+            .windows(2)
+            .any(|w| w[0] != w[1]));
+
+        assert_eq!(
+            random_walk(7, 1),
+            Err("There is no walk of that length that gets back to where we started")
+        );
+        assert_eq!(
+            random_walk(70, 1),
+            Err("There are too many walks of that length to pick from")
+        );
+        assert_eq!(
+            random_walk(100_000, 1),
+            Err("There are too many walks of that length to pick from")
+        );
+        // Just below the cutoff, so most draws land in the top of the range
+        assert!(random_walk(66, 1).is_ok_and(|walk| ends_at_home(&walk)));
+    }
+}
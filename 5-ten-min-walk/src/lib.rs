@@ -0,0 +1,208 @@
+use std::fmt;
+
+mod cli;
+mod generator;
+mod map;
+mod movement;
+mod session;
+mod validate;
+
+pub use crate::cli::{
+    parse_time_of_walk, parse_time_of_walk_with, run, run_on_map, run_with, CliError,
+};
+pub use crate::generator::{
+    count_walks, count_walks_with, random_walk, random_walk_with, walks, walks_with, Walks,
+};
+pub use crate::map::{MapError, WalkMap};
+pub use crate::movement::Movement;
+pub use crate::session::{SessionState, WalkSession};
+pub use crate::validate::{
+    displacement, displacement_with, validate_walk, validate_walk_with, WalkError,
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+pub const ALL_DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::East,
+    Direction::West,
+];
+
+impl Direction {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Direction::North => "North",
+            Direction::South => "South",
+            Direction::East => "East",
+            Direction::West => "West",
+            Direction::NorthEast => "North-East",
+            Direction::NorthWest => "North-West",
+            Direction::SouthEast => "South-East",
+            Direction::SouthWest => "South-West",
+        }
+    }
+
+    pub fn direction_diff(&self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::South => (0, -1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+            Direction::NorthEast => (1, 1),
+            Direction::NorthWest => (-1, 1),
+            Direction::SouthEast => (1, -1),
+            Direction::SouthWest => (-1, -1),
+        }
+    }
+
+    pub fn step_from(&self, position: (i32, i32)) -> (i32, i32) {
+        let diff = self.direction_diff();
+        (position.0 + diff.0, position.1 + diff.1)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::North => write!(f, "North"),
+            Direction::South => write!(f, "South"),
+            Direction::East => write!(f, "East"),
+            Direction::West => write!(f, "West"),
+            Direction::NorthEast => write!(f, "North-East"),
+            Direction::NorthWest => write!(f, "North-West"),
+            Direction::SouthEast => write!(f, "South-East"),
+            Direction::SouthWest => write!(f, "South-West"),
+        }
+    }
+}
+
+pub fn options_availible(
+    current_position: (i32, i32),
+    minutes_left: u32,
+) -> Option<Vec<Direction>> {
+    Movement::Compass.options_availible(current_position, minutes_left)
+}
+
+// North-East can be given as "ne", "northeast" or "North-East", and
+// otherwise the first letter picks the direction
+pub fn chosen_direction(input: &str) -> Option<Direction> {
+    let lower: String = input
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let (north_or_south, rest) = if let Some(rest) = lower.strip_prefix("north") {
+        (Some(Direction::North), rest)
+    } else if let Some(rest) = lower.strip_prefix("south") {
+        (Some(Direction::South), rest)
+    } else if let Some(rest) = lower.strip_prefix('n') {
+        (Some(Direction::North), rest)
+    } else if let Some(rest) = lower.strip_prefix('s') {
+        (Some(Direction::South), rest)
+    } else {
+        (None, lower.as_str())
+    };
+
+    match (north_or_south, rest) {
+        (Some(Direction::North), "e") | (Some(Direction::North), "east") => {
+            Some(Direction::NorthEast)
+        }
+        (Some(Direction::North), "w") | (Some(Direction::North), "west") => {
+            Some(Direction::NorthWest)
+        }
+        (Some(Direction::South), "e") | (Some(Direction::South), "east") => {
+            Some(Direction::SouthEast)
+        }
+        (Some(Direction::South), "w") | (Some(Direction::South), "west") => {
+            Some(Direction::SouthWest)
+        }
+        (Some(direction), _) => Some(direction),
+        (None, _) if rest.starts_with('e') => Some(Direction::East),
+        (None, _) if rest.starts_with('w') => Some(Direction::West),
+        (None, _) => None,
+    }
+}
+
+// Like chosen_direction, but only the movement's directions. Without the
+// diagonals "ne" or "sw" still picks North or South by the first letter.
+pub fn chosen_direction_with(input: &str, movement: Movement) -> Option<Direction> {
+    let direction = match chosen_direction(input)? {
+        direction if movement.directions().contains(&direction) => direction,
+        Direction::NorthEast | Direction::NorthWest => Direction::North,
+        Direction::SouthEast | Direction::SouthWest => Direction::South,
+        _ => return None,
+    };
+
+    Some(direction).filter(|direction| movement.directions().contains(direction))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_all_options_availible() {
+        let output = options_availible((0, 0), 10);
+        let all_options = vec![
+            Direction::North,
+            Direction::South,
+            Direction::East,
+            Direction::West,
+        ];
+        assert_eq!(output, Some(all_options));
+    }
+
+    #[test]
+    fn test_impossible_options() {
+        assert_eq!(options_availible((0, -10), 1), None);
+        assert_eq!(options_availible((1, 10), 10), None);
+        assert_eq!(options_availible((-1, 10), 10), None);
+    }
+
+    #[test]
+    fn test_already_at_origin_and_no_minutes_left() {
+        assert_eq!(options_availible((0, 0), 0), Some(vec![]));
+    }
+
+    #[test]
+    fn test_chosen_direction() {
+        assert_eq!(chosen_direction("north"), Some(Direction::North));
+        assert_eq!(chosen_direction("Nope"), Some(Direction::North));
+        assert_eq!(chosen_direction("ne"), Some(Direction::NorthEast));
+        assert_eq!(chosen_direction("South-West"), Some(Direction::SouthWest));
+        assert_eq!(chosen_direction("west"), Some(Direction::West));
+        assert_eq!(chosen_direction("up"), None);
+
+        assert_eq!(
+            chosen_direction_with("ne", Movement::Compass),
+            Some(Direction::North)
+        );
+        assert_eq!(
+            chosen_direction_with("South-West", Movement::Compass),
+            Some(Direction::South)
+        );
+        assert_eq!(
+            chosen_direction_with("ne", Movement::Hex),
+            Some(Direction::NorthEast)
+        );
+        assert_eq!(chosen_direction_with("east", Movement::Hex), None);
+    }
+
+    #[test]
+    fn test_north_only_option() {
+        let output = options_availible((0, -1), 1);
+        assert_eq!(output, Some(vec![Direction::North]));
+    }
+}
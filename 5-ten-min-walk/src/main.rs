@@ -1,220 +1,140 @@
-use std::fmt;
+use std::env;
 use std::io;
-use std::io::prelude::*;
-
-fn parse_time_of_walk(user_input: &str) -> Result<u32, &'static str> {
-    let parsed_input_result: Result<u32, _> = user_input.parse();
-    match parsed_input_result {
-        Ok(i) => {
-            if i % 2 == 0 {
-                Ok(i)
-            } else {
-                Err("Walk time must be an even number or we will not be able to get back to where we started")
-            }
-        }
-        Err(_) => Err("Could not parse the walk time"),
-    }
-}
+use std::process;
 
-#[derive(Debug, PartialEq, Clone)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
+use ten_min_walk::{run_on_map, run_with, Movement, WalkMap};
 
-impl Direction {
-    fn will_take_me_home(&self, current_position: (i32, i32)) -> bool {
-        match self {
-            Direction::North => current_position.1 < 0,
-            Direction::South => current_position.1 > 0,
-            Direction::East => current_position.0 < 0,
-            Direction::West => current_position.0 > 0,
-        }
-    }
+const USAGE: &str = "Usage: ten-min-walk [--diagonal|--hex|--map FILE]
 
-    fn to_str(&self) -> &'static str {
-        match self {
-            Direction::North => "North",
-            Direction::South => "South",
-            Direction::East => "East",
-            Direction::West => "West",
-        }
-    }
+Walks on a square grid, or also across blocks with --diagonal, or between
+hexagonal blocks with --hex. With --map only the streets drawn in FILE can
+be walked.";
 
-    fn direction_diff(&self) -> (i32, i32) {
-        match self {
-            Direction::North => (0, 1),
-            Direction::South => (0, -1),
-            Direction::East => (1, 0),
-            Direction::West => (-1, 0),
-        }
-    }
-}
+const EXIT_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
 
-impl fmt::Display for Direction {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Direction::North => write!(f, "North"),
-            Direction::South => write!(f, "South"),
-            Direction::East => write!(f, "East"),
-            Direction::West => write!(f, "West"),
-        }
-    }
+#[derive(Debug, PartialEq)]
+struct Options {
+    movement: Movement,
+    map: Option<String>,
+    help: bool,
 }
 
-fn options_availible(current_position: (i32, i32), minutes_left: u32) -> Option<Vec<Direction>> {
-    if current_position == (0, 0) && minutes_left == 0 {
-        return Some(vec![]);
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options {
+        movement: Movement::Compass,
+        map: None,
+        help: false,
+    };
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let map = if arg == "--map" {
+            Some(args.next().ok_or("--map needs a file")?)
+        } else {
+            arg.strip_prefix("--map=").map(str::to_owned)
+        };
+
+        match arg.as_str() {
+            _ if map.is_some() => options.map = map,
+            "--diagonal" => options.movement = Movement::Diagonal,
+            "--hex" => options.movement = Movement::Hex,
+            "--help" | "-h" => options.help = true,
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
     }
 
-    let distance_from_origin = current_position.0.abs() + current_position.1.abs();
-
-    if distance_from_origin > minutes_left as i32 {
-        None
-    } else {
-        let all_options: Vec<Direction> = vec![
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ];
-
-        let can_take_detour = distance_from_origin < minutes_left as i32;
-
-        let options = all_options
-            .iter()
-            .filter(|direction| direction.will_take_me_home(current_position) || can_take_detour)
-            .cloned()
-            .collect();
-        Some(options)
+    if options.map.is_some() && options.movement != Movement::Compass {
+        return Err("Maps can only be walked along their streets".to_owned());
     }
-}
 
-fn chosen_direction(input: &str) -> Option<Direction> {
-    let lower = input.to_ascii_lowercase();
-    if lower.starts_with("n") {
-        Some(Direction::North)
-    } else if lower.starts_with("e") {
-        Some(Direction::East)
-    } else if lower.starts_with("w") {
-        Some(Direction::West)
-    } else if lower.starts_with("s") {
-        Some(Direction::South)
-    } else {
-        None
-    }
+    Ok(options)
 }
 
-fn directions_to_string(path: &Vec<Direction>) -> String {
-    path.iter()
-        .map(Direction::to_str)
-        .collect::<Vec<_>>()
-        .join(", ")
-}
-
-fn main() -> Result<(), &'static str> {
-    println!("Hey I heard you want to take a walk!");
-    println!("How long (in minutes) should we walk for?");
-    let stdin = io::stdin();
-    let mut stdin_lines = stdin.lock().lines();
-    let user_input_minutes = stdin_lines.next().unwrap().unwrap();
-
-    let mut minutes_left = parse_time_of_walk(&user_input_minutes)?;
-    let mut current_position = (0, 0);
-    let mut path: Vec<Direction> = vec![];
-
-    let mut options = options_availible(current_position, minutes_left);
-    while minutes_left > 0 && options.is_some() {
-        let unwrapped_options = options.unwrap();
-
-        println!("\nYou have {} minutes left", minutes_left);
-        println!("Which direction would you like to go?");
-        println!(
-            "So far you'r path has been: {}",
-            directions_to_string(&path)
-        );
-        println!(
-            "Your current options are: {}",
-            directions_to_string(&unwrapped_options)
-        );
-
-        let mut chosen_direction_option = chosen_direction(&stdin_lines.next().unwrap().unwrap());
-        while chosen_direction_option.is_none() {
-            chosen_direction_option = chosen_direction(&stdin_lines.next().unwrap().unwrap());
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(EXIT_USAGE);
         }
-
-        let chosen_direction = chosen_direction_option.unwrap();
-        println!("You chose: {}", chosen_direction);
-
-        current_position.0 += chosen_direction.direction_diff().0;
-        current_position.1 += chosen_direction.direction_diff().1;
-        path.push(chosen_direction);
-        minutes_left -= 1;
-        options = options_availible(current_position, minutes_left);
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
     }
 
-    if minutes_left == 0 {
-        println!(
-            "YAY we made it! Here is the path we took: {}",
-            directions_to_string(&path)
-        );
-    } else if options.is_none() {
-        println!("OH NO! We won't be able to make it back in time! Try another path.");
-        println!(
-            "Here is what you tried this time: {}",
-            directions_to_string(&path)
-        );
+    let stdin = io::stdin();
+    let result = match &options.map {
+        Some(path) => match WalkMap::from_file(path) {
+            Ok(map) => run_on_map(stdin.lock(), io::stdout(), map),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                process::exit(EXIT_FAILED);
+            }
+        },
+        None => run_with(stdin.lock(), io::stdout(), options.movement),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
     #[test]
-    fn parse_time_of_walk_test() {
+    fn it_parses_movements() {
         assert_eq!(
-            parse_time_of_walk("3"),
-            Err("Walk time must be an even number or we will not be able to get back to where we started")
+            parse_args(args(&[])),
+            Ok(Options {
+                movement: Movement::Compass,
+                map: None,
+                help: false,
+            })
         );
         assert_eq!(
-            parse_time_of_walk("five"),
-            Err("Could not parse the walk time")
+            parse_args(args(&["--hex"])).map(|o| o.movement),
+            Ok(Movement::Hex)
+        );
+        assert_eq!(
+            parse_args(args(&["--diagonal"])).map(|o| o.movement),
+            Ok(Movement::Diagonal)
+        );
+        assert_eq!(
+            parse_args(args(&["--map", "block.txt"])).map(|o| o.map),
+            Ok(Some("block.txt".to_owned()))
+        );
+        assert_eq!(
+            parse_args(args(&["--map=block.txt"])).map(|o| o.map),
+            Ok(Some("block.txt".to_owned()))
         );
-        assert_eq!(parse_time_of_walk("4"), Ok(4));
-    }
-
-    #[test]
-    fn test_all_options_availible() {
-        let output = options_availible((0, 0), 10);
-        let all_options = vec![
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ];
-        assert_eq!(output, Some(all_options));
-    }
-
-    #[test]
-    fn test_impossible_options() {
-        assert_eq!(options_availible((0, -10), 1), None);
-        assert_eq!(options_availible((1, 10), 10), None);
-        assert_eq!(options_availible((-1, 10), 10), None);
-    }
-
-    #[test]
-    fn test_already_at_origin_and_no_minutes_left() {
-        assert_eq!(options_availible((0, 0), 0), Some(vec![]));
     }
 
     #[test]
-    fn test_north_only_option() {
-        let output = options_availible((0, -1), 1);
-        assert_eq!(output, Some(vec![Direction::North]));
+    fn it_rejects_bad_arguments() {
+        assert_eq!(
+            parse_args(args(&["--hexx"])),
+            Err("Unknown option: --hexx".to_owned())
+        );
+        assert_eq!(
+            parse_args(args(&["--hex", "10"])),
+            Err("Unexpected argument: 10".to_owned())
+        );
+        assert_eq!(
+            parse_args(args(&["--map"])),
+            Err("--map needs a file".to_owned())
+        );
+        assert_eq!(
+            parse_args(args(&["--map", "block.txt", "--hex"])),
+            Err("Maps can only be walked along their streets".to_owned())
+        );
     }
 }
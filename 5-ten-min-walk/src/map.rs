@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Direction, ALL_DIRECTIONS};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MapError {
+    Io(String),
+    InvalidCharacter {
+        line_number: usize,
+        column: usize,
+        character: char,
+    },
+    MissingHome,
+    DuplicateHome {
+        line_number: usize,
+        column: usize,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Io(e) => write!(f, "Could not read the map: {}", e),
+            MapError::InvalidCharacter {
+                line_number,
+                column,
+                character,
+            } => write!(
+                f,
+                "Line {}, column {}: '{}' does not belong there",
+                line_number, column, character
+            ),
+            MapError::MissingHome => write!(f, "The map has no home marked with H"),
+            MapError::DuplicateHome {
+                line_number,
+                column,
+            } => write!(
+                f,
+                "Line {}, column {}: the map already has a home",
+                line_number, column
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+impl From<io::Error> for MapError {
+    fn from(e: io::Error) -> Self {
+        MapError::Io(e.to_string())
+    }
+}
+
+// The streets around home and how many minutes each takes to walk. A street
+// that is only in here one way round is one-way.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct WalkMap {
+    streets: HashMap<((i32, i32), Direction), u32>,
+}
+
+impl WalkMap {
+    // Corners sit on even lines and columns, with the streets between them:
+    //
+    //   H-.-.    H is home, . a corner and # somewhere that can't be walked.
+    //   |   3    - and | are streets taking a minute, and a digit is a
+    //   .-#-.    street taking that many minutes. > < ^ and v are one-way
+    //   ^   |    streets taking a minute, and a space is no street at all.
+    //   .<.-.
+    pub fn parse(map: &str) -> Result<Self, MapError> {
+        let lines: Vec<Vec<char>> = map.lines().map(|line| line.chars().collect()).collect();
+
+        let mut home = None;
+        for (row, line) in lines.iter().enumerate().step_by(2) {
+            for (col, &character) in line.iter().enumerate().step_by(2) {
+                match character {
+                    '.' | '#' => {}
+                    'H' if home.is_none() => home = Some((row, col)),
+                    'H' => {
+                        return Err(MapError::DuplicateHome {
+                            line_number: row + 1,
+                            column: col + 1,
+                        })
+                    }
+                    _ => {
+                        return Err(MapError::InvalidCharacter {
+                            line_number: row + 1,
+                            column: col + 1,
+                            character,
+                        })
+                    }
+                }
+            }
+        }
+        let home = home.ok_or(MapError::MissingHome)?;
+
+        let is_corner = |row: usize, col: usize| {
+            lines
+                .get(row)
+                .and_then(|line| line.get(col))
+                .is_some_and(|&c| c == '.' || c == 'H')
+        };
+        let position = |row: usize, col: usize| {
+            (
+                (col as i32 - home.1 as i32) / 2,
+                (home.0 as i32 - row as i32) / 2,
+            )
+        };
+
+        let mut result = WalkMap::default();
+        for (row, line) in lines.iter().enumerate() {
+            for (col, &character) in line.iter().enumerate() {
+                let invalid = MapError::InvalidCharacter {
+                    line_number: row + 1,
+                    column: col + 1,
+                    character,
+                };
+
+                // Each street runs from one corner forward to the next, going
+                // east along a line or south down a column
+                let (from, to, forward, back) = match (row % 2, col % 2) {
+                    (0, 0) => continue,
+                    (0, _) => (
+                        (row, col - 1),
+                        (row, col + 1),
+                        Direction::East,
+                        Direction::West,
+                    ),
+                    (_, 0) => (
+                        (row - 1, col),
+                        (row + 1, col),
+                        Direction::South,
+                        Direction::North,
+                    ),
+                    _ if character == ' ' || character == '+' => continue,
+                    _ => return Err(invalid),
+                };
+                let (forward_time, back_time) = match (character, forward) {
+                    (' ', _) => continue,
+                    ('-', Direction::East) | ('|', Direction::South) => (Some(1), Some(1)),
+                    ('>', Direction::East) | ('v', Direction::South) => (Some(1), None),
+                    ('<', Direction::East) | ('^', Direction::South) => (None, Some(1)),
+                    _ => match character.to_digit(10) {
+                        Some(minutes) if minutes > 0 => (Some(minutes), Some(minutes)),
+                        _ => return Err(invalid),
+                    },
+                };
+                if !is_corner(from.0, from.1) || !is_corner(to.0, to.1) {
+                    continue;
+                }
+
+                let from = position(from.0, from.1);
+                let to = position(to.0, to.1);
+                if let Some(minutes) = forward_time {
+                    result.streets.insert((from, forward), minutes);
+                }
+                if let Some(minutes) = back_time {
+                    result.streets.insert((to, back), minutes);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, MapError> {
+        WalkMap::parse(&fs::read_to_string(path)?)
+    }
+
+    // How many minutes it takes to walk that way, or None if there's no
+    // street to walk down
+    pub fn travel_time(&self, position: (i32, i32), direction: Direction) -> Option<u32> {
+        self.streets.get(&(position, direction)).cloned()
+    }
+
+    // Like options_availible, but only offering streets that can be walked
+    // and still leave a way home in exactly the minutes left
+    pub fn options_availible(
+        &self,
+        current_position: (i32, i32),
+        minutes_left: u32,
+    ) -> Option<Vec<Direction>> {
+        let mut memo = HashMap::new();
+        if !self.can_get_home(current_position, minutes_left, &mut memo) {
+            return None;
+        }
+
+        let mut options = vec![];
+        for &direction in ALL_DIRECTIONS.iter() {
+            let minutes_after = match self.travel_time(current_position, direction) {
+                Some(minutes) if minutes <= minutes_left => minutes_left - minutes,
+                _ => continue,
+            };
+            if self.can_get_home(
+                direction.step_from(current_position),
+                minutes_after,
+                &mut memo,
+            ) {
+                options.push(direction);
+            }
+        }
+
+        Some(options)
+    }
+
+    fn can_get_home(
+        &self,
+        position: (i32, i32),
+        minutes_left: u32,
+        memo: &mut HashMap<((i32, i32), u32), bool>,
+    ) -> bool {
+        if minutes_left == 0 {
+            return position == (0, 0);
+        }
+        if let Some(&can) = memo.get(&(position, minutes_left)) {
+            return can;
+        }
+
+        let can =
+            ALL_DIRECTIONS
+                .iter()
+                .any(|&direction| match self.travel_time(position, direction) {
+                    Some(minutes) if minutes <= minutes_left => self.can_get_home(
+                        direction.step_from(position),
+                        minutes_left - minutes,
+                        memo,
+                    ),
+                    _ => false,
+                });
+        memo.insert((position, minutes_left), can);
+        can
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    // The only way round the block is clockwise, and takes 10 minutes
+    const MAP: &str = "H-.-.
+|   3
+.-#-.
+^   |
+.<.-.";
+
+    #[test]
+    fn it_reads_maps() -> Result<(), MapError> {
+        let map = WalkMap::parse(MAP)?;
+
+        assert_eq!(map.travel_time((0, 0), Direction::East), Some(1));
+        assert_eq!(map.travel_time((1, 0), Direction::West), Some(1));
+        assert_eq!(map.travel_time((2, 0), Direction::South), Some(3));
+        assert_eq!(map.travel_time((2, -1), Direction::North), Some(3));
+        assert_eq!(map.travel_time((1, -2), Direction::West), Some(1));
+        assert_eq!(map.travel_time((0, -2), Direction::East), None);
+        assert_eq!(map.travel_time((0, -1), Direction::South), None);
+        assert_eq!(map.travel_time((0, -1), Direction::East), None);
+        assert_eq!(map.travel_time((0, 0), Direction::North), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_only_offers_streets_that_lead_home() -> Result<(), MapError> {
+        let map = WalkMap::parse(MAP)?;
+
+        assert_eq!(
+            map.options_availible((0, 0), 10),
+            Some(vec![Direction::South, Direction::East])
+        );
+        assert_eq!(map.options_availible((0, 0), 3), None);
+        assert_eq!(map.options_availible((0, 0), 0), Some(vec![]));
+        assert_eq!(
+            map.options_availible((1, -2), 3),
+            Some(vec![Direction::West])
+        );
+        assert_eq!(
+            map.options_availible((0, -1), 1),
+            Some(vec![Direction::North])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_walks_round_the_block() -> Result<(), &'static str> {
+        let mut session = WalkSession::on_map(
+            WalkMap::parse(MAP).map_err(|_| "The map could not be read")?,
+            10,
+        );
+        assert_eq!(
+            session.step(Direction::North),
+            Err("There is no street that way")
+        );
+
+        session.step(Direction::East)?;
+        session.step(Direction::East)?;
+        session.step(Direction::South)?;
+        assert_eq!(session.minutes_left(), 5);
+        for &direction in &[
+            Direction::South,
+            Direction::West,
+            Direction::West,
+            Direction::North,
+        ] {
+            assert_eq!(session.step(direction)?, SessionState::Walking);
+        }
+        assert_eq!(session.step(Direction::North)?, SessionState::Home);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_read_bad_maps() {
+        assert_eq!(
+            WalkMap::parse("H-x"),
+            Err(MapError::InvalidCharacter {
+                line_number: 1,
+                column: 3,
+                character: 'x'
+            })
+        );
+        // Streets that lead off the map go nowhere
+        assert_eq!(
+            WalkMap::parse("H>.\n^").map(|map| map.travel_time((0, 0), Direction::South)),
+            Ok(None)
+        );
+        assert_eq!(
+            WalkMap::parse("H|."),
+            Err(MapError::InvalidCharacter {
+                line_number: 1,
+                column: 2,
+                character: '|'
+            })
+        );
+        assert_eq!(WalkMap::parse(".-."), Err(MapError::MissingHome));
+        assert_eq!(
+            WalkMap::parse("H-H"),
+            Err(MapError::DuplicateHome {
+                line_number: 1,
+                column: 3
+            })
+        );
+    }
+}
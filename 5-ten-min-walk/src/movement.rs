@@ -0,0 +1,197 @@
+use crate::{Direction, ALL_DIRECTIONS};
+
+const DIAGONAL_DIRECTIONS: [Direction; 8] = [
+    Direction::North,
+    Direction::South,
+    Direction::East,
+    Direction::West,
+    Direction::NorthEast,
+    Direction::NorthWest,
+    Direction::SouthEast,
+    Direction::SouthWest,
+];
+
+const HEX_DIRECTIONS: [Direction; 6] = [
+    Direction::North,
+    Direction::South,
+    Direction::NorthEast,
+    Direction::NorthWest,
+    Direction::SouthEast,
+    Direction::SouthWest,
+];
+
+// How a walker can move between blocks. Compass moves are along the four
+// streets, diagonal moves can also cut across a block, and hex moves are
+// between the six neighbours of a hexagonal block with flat tops.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum Movement {
+    #[default]
+    Compass,
+    Diagonal,
+    Hex,
+}
+
+impl Movement {
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Movement::Compass => &ALL_DIRECTIONS,
+            Movement::Diagonal => &DIAGONAL_DIRECTIONS,
+            Movement::Hex => &HEX_DIRECTIONS,
+        }
+    }
+
+    // Hex positions are axial, so North-East is (1, 0) and South-East is
+    // (1, -1). None if the direction can't be taken with this movement.
+    pub fn direction_diff(self, direction: Direction) -> Option<(i32, i32)> {
+        if !self.directions().contains(&direction) {
+            return None;
+        }
+
+        match (self, direction) {
+            (Movement::Hex, Direction::NorthEast) => Some((1, 0)),
+            (Movement::Hex, Direction::SouthWest) => Some((-1, 0)),
+            _ => Some(direction.direction_diff()),
+        }
+    }
+
+    pub fn step_from(self, position: (i32, i32), direction: Direction) -> Option<(i32, i32)> {
+        let diff = self.direction_diff(direction)?;
+        Some((position.0 + diff.0, position.1 + diff.1))
+    }
+
+    // The fewest moves it takes to get home
+    pub fn distance(self, position: (i32, i32)) -> u32 {
+        let (x, y) = (position.0.unsigned_abs(), position.1.unsigned_abs());
+        match self {
+            Movement::Compass => x + y,
+            Movement::Diagonal => x.max(y),
+            Movement::Hex => (x + y + (position.0 + position.1).unsigned_abs()) / 2,
+        }
+    }
+
+    // Compass walks can only get home in an even number of moves. The other
+    // movements can go round a triangle, so only a single move is too short.
+    pub fn can_get_home(self, position: (i32, i32), minutes_left: u32) -> bool {
+        let distance = self.distance(position);
+        if distance > minutes_left {
+            return false;
+        }
+
+        match self {
+            Movement::Compass => (minutes_left - distance).is_multiple_of(2),
+            Movement::Diagonal | Movement::Hex => distance > 0 || minutes_left != 1,
+        }
+    }
+
+    pub fn allows_walk_of(self, minutes: u32) -> bool {
+        self.can_get_home((0, 0), minutes)
+    }
+
+    // The directions that still allow a return home in exactly the minutes
+    // left, or None if that can no longer be done
+    pub fn options_availible(
+        self,
+        current_position: (i32, i32),
+        minutes_left: u32,
+    ) -> Option<Vec<Direction>> {
+        if !self.can_get_home(current_position, minutes_left) {
+            return None;
+        }
+        if minutes_left == 0 {
+            return Some(vec![]);
+        }
+
+        let options = self
+            .directions()
+            .iter()
+            .filter(|&&direction| {
+                self.step_from(current_position, direction)
+                    .is_some_and(|next| self.can_get_home(next, minutes_left - 1))
+            })
+            .cloned()
+            .collect();
+        Some(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn it_measures_distance_home() {
+        assert_eq!(Movement::Compass.distance((2, -3)), 5);
+        assert_eq!(Movement::Diagonal.distance((2, -3)), 3);
+        assert_eq!(Movement::Hex.distance((2, -3)), 3);
+        assert_eq!(Movement::Hex.distance((2, 3)), 5);
+
+        assert_eq!(
+            Movement::Compass.step_from((0, 0), Direction::NorthEast),
+            None
+        );
+        assert_eq!(Movement::Hex.step_from((0, 0), Direction::East), None);
+        assert_eq!(
+            Movement::Hex.step_from((0, 0), Direction::SouthEast),
+            Some((1, -1))
+        );
+    }
+
+    #[test]
+    fn it_allows_odd_walks_where_the_geometry_does() {
+        assert!(!Movement::Compass.allows_walk_of(3));
+        assert!(Movement::Compass.allows_walk_of(4));
+        for &movement in &[Movement::Diagonal, Movement::Hex] {
+            assert!(movement.allows_walk_of(0));
+            assert!(!movement.allows_walk_of(1));
+            assert!(movement.allows_walk_of(3));
+        }
+
+        assert_eq!(count_walks_with(3, Movement::Compass), Some(0));
+        // Round one of the triangles either way
+        assert_eq!(count_walks_with(3, Movement::Hex), Some(12));
+        assert_eq!(count_walks_with(2, Movement::Diagonal), Some(8));
+        for walk in walks_with(3, Movement::Diagonal) {
+            assert_eq!(validate_walk_with(&walk, 3, Movement::Diagonal), Ok(walk));
+        }
+        assert_eq!(
+            walks_with(5, Movement::Hex).count() as u128,
+            count_walks_with(5, Movement::Hex).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_offers_the_options_for_each_movement() {
+        assert_eq!(
+            Movement::Diagonal.options_availible((1, 1), 1),
+            Some(vec![Direction::SouthWest])
+        );
+        assert_eq!(Movement::Diagonal.options_availible((0, 0), 1), None);
+        assert_eq!(
+            Movement::Hex.options_availible((0, 1), 2),
+            Some(vec![Direction::SouthEast, Direction::SouthWest])
+        );
+        assert_eq!(Movement::Hex.options_availible((2, 0), 1), None);
+        assert_eq!(
+            Movement::Compass.options_availible((0, -1), 1),
+            options_availible((0, -1), 1)
+        );
+    }
+
+    #[test]
+    fn it_walks_with_each_movement() -> Result<(), &'static str> {
+        let mut session = WalkSession::with_movement(Movement::Hex, 3);
+        assert_eq!(
+            session.step(Direction::East),
+            Err("You can't walk that way")
+        );
+        session.step(Direction::North)?;
+        session.step(Direction::SouthEast)?;
+        assert_eq!(session.position(), (1, 0));
+        assert_eq!(session.step(Direction::SouthWest)?, SessionState::Home);
+
+        assert_eq!(parse_time_of_walk_with("3", Movement::Diagonal), Ok(3));
+        assert!(parse_time_of_walk_with("1", Movement::Hex).is_err());
+
+        Ok(())
+    }
+}
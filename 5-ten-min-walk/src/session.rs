@@ -0,0 +1,148 @@
+use crate::{Direction, Movement, WalkMap};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SessionState {
+    Walking,
+    Home,
+    // Too far from home to make it back in the minutes left
+    Stranded,
+}
+
+// One walk as it's taken. Any direction can be chosen, not just the options
+// offered, so a walker can end up stranded.
+#[derive(Debug, PartialEq, Clone)]
+pub struct WalkSession {
+    position: (i32, i32),
+    minutes_left: u32,
+    path: Vec<Direction>,
+    options: Option<Vec<Direction>>,
+    movement: Movement,
+    map: Option<WalkMap>,
+}
+
+impl WalkSession {
+    pub fn new(minutes: u32) -> Self {
+        WalkSession::with_movement(Movement::Compass, minutes)
+    }
+
+    pub fn with_movement(movement: Movement, minutes: u32) -> Self {
+        WalkSession {
+            position: (0, 0),
+            minutes_left: minutes,
+            path: vec![],
+            options: movement.options_availible((0, 0), minutes),
+            movement,
+            map: None,
+        }
+    }
+
+    // Only the map's streets can be walked, each taking its own time
+    pub fn on_map(map: WalkMap, minutes: u32) -> Self {
+        WalkSession {
+            position: (0, 0),
+            minutes_left: minutes,
+            path: vec![],
+            options: map.options_availible((0, 0), minutes),
+            movement: Movement::Compass,
+            map: Some(map),
+        }
+    }
+
+    pub fn position(&self) -> (i32, i32) {
+        self.position
+    }
+
+    pub fn minutes_left(&self) -> u32 {
+        self.minutes_left
+    }
+
+    pub fn path(&self) -> &[Direction] {
+        &self.path
+    }
+
+    // The directions that still allow a return home, or None once stranded
+    pub fn options(&self) -> Option<&[Direction]> {
+        self.options.as_deref()
+    }
+
+    pub fn state(&self) -> SessionState {
+        match self.options {
+            None => SessionState::Stranded,
+            Some(_) if self.minutes_left == 0 => SessionState::Home,
+            Some(_) => SessionState::Walking,
+        }
+    }
+
+    pub fn step(&mut self, direction: Direction) -> Result<SessionState, &'static str> {
+        if self.state() != SessionState::Walking {
+            return Err("The walk is already over");
+        }
+
+        let (position, minutes) = match &self.map {
+            Some(map) => (
+                direction.step_from(self.position),
+                map.travel_time(self.position, direction)
+                    .ok_or("There is no street that way")?,
+            ),
+            None => (
+                self.movement
+                    .step_from(self.position, direction)
+                    .ok_or("You can't walk that way")?,
+                1,
+            ),
+        };
+        if minutes > self.minutes_left {
+            return Err("There isn't time to walk that way");
+        }
+
+        self.position = position;
+        self.path.push(direction);
+        self.minutes_left -= minutes;
+        self.options = match &self.map {
+            Some(map) => map.options_availible(self.position, self.minutes_left),
+            None => self
+                .movement
+                .options_availible(self.position, self.minutes_left),
+        };
+
+        Ok(self.state())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn it_walks_home() -> Result<(), &'static str> {
+        let mut session = WalkSession::new(2);
+        assert_eq!(session.state(), SessionState::Walking);
+        assert_eq!(session.options().map(|o| o.len()), Some(4));
+
+        assert_eq!(session.step(Direction::East)?, SessionState::Walking);
+        assert_eq!(session.position(), (1, 0));
+        assert_eq!(session.minutes_left(), 1);
+        assert_eq!(session.options(), Some(&[Direction::West][..]));
+
+        assert_eq!(session.step(Direction::West)?, SessionState::Home);
+        assert_eq!(session.path(), &[Direction::East, Direction::West]);
+        assert_eq!(
+            session.step(Direction::West),
+            Err("The walk is already over")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_gets_stranded() -> Result<(), &'static str> {
+        let mut session = WalkSession::new(4);
+        session.step(Direction::North)?;
+        session.step(Direction::North)?;
+        assert_eq!(session.step(Direction::North)?, SessionState::Stranded);
+        assert_eq!(session.options(), None);
+        assert_eq!(session.minutes_left(), 1);
+
+        Ok(())
+    }
+}
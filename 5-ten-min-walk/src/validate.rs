@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::{chosen_direction, Direction, Movement};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WalkError {
+    UnknownDirection {
+        index: usize,
+        input: String,
+    },
+    UnavailableDirection {
+        index: usize,
+        direction: Direction,
+    },
+    WrongLength {
+        minutes: u32,
+        steps: usize,
+    },
+    NotHome {
+        displacement: (i32, i32),
+        movement: Movement,
+    },
+}
+
+impl fmt::Display for WalkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WalkError::UnknownDirection { index, input } => {
+                write!(f, "Step {} is not a direction: {}", index + 1, input)
+            }
+            WalkError::UnavailableDirection { index, direction } => {
+                write!(
+                    f,
+                    "Step {} can't go {} with this movement",
+                    index + 1,
+                    direction
+                )
+            }
+            WalkError::WrongLength { minutes, steps } => {
+                write!(f, "The walk takes {} minutes instead of {}", steps, minutes)
+            }
+            // Hex positions are axial, so only the distance makes sense
+            WalkError::NotHome {
+                displacement,
+                movement: Movement::Hex,
+            } => write!(
+                f,
+                "The walk ends {} blocks away from where it started",
+                Movement::Hex.distance(*displacement)
+            ),
+            WalkError::NotHome { displacement, .. } => write!(
+                f,
+                "The walk ends {} blocks east and {} blocks north of where it started",
+                displacement.0, displacement.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WalkError {}
+
+// How far east and north of the start the path ends up on a square grid
+pub fn displacement(path: &[Direction]) -> (i32, i32) {
+    path.iter().fold((0, 0), |(x, y), direction| {
+        let diff = direction.direction_diff();
+        (x + diff.0, y + diff.1)
+    })
+}
+
+// Where the path ends up with this movement, or None if it takes a direction
+// the movement doesn't have
+pub fn displacement_with(path: &[Direction], movement: Movement) -> Option<(i32, i32)> {
+    path.iter().try_fold((0, 0), |position, &direction| {
+        movement.step_from(position, direction)
+    })
+}
+
+// Checks a walk given as direction names or letters, like ['n', 's'] or
+// ["North", "South"], takes exactly the minutes and ends back home
+pub fn validate_walk<T: ToString>(walk: &[T], minutes: u32) -> Result<Vec<Direction>, WalkError> {
+    validate_walk_with(walk, minutes, Movement::Compass)
+}
+
+pub fn validate_walk_with<T: ToString>(
+    walk: &[T],
+    minutes: u32,
+    movement: Movement,
+) -> Result<Vec<Direction>, WalkError> {
+    let path = walk
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let input = step.to_string();
+            chosen_direction(input.trim()).ok_or(WalkError::UnknownDirection { index, input })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if path.len() != minutes as usize {
+        return Err(WalkError::WrongLength {
+            minutes,
+            steps: path.len(),
+        });
+    }
+    let mut position = (0, 0);
+    for (index, &direction) in path.iter().enumerate() {
+        position = movement
+            .step_from(position, direction)
+            .ok_or(WalkError::UnavailableDirection { index, direction })?;
+    }
+    match position {
+        (0, 0) => Ok(path),
+        displacement => Err(WalkError::NotHome {
+            displacement,
+            movement,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn it_validates_walks() {
+        let walk = ['n', 's', 'n', 's', 'n', 's', 'n', 's', 'n', 's'];
+        assert_eq!(validate_walk(&walk, 10).map(|path| path.len()), Ok(10));
+        assert_eq!(
+            validate_walk(&["North", "east", "S", "West"], 4),
+            Ok(vec![
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West
+            ])
+        );
+        assert_eq!(validate_walk::<char>(&[], 0), Ok(vec![]));
+    }
+
+    #[test]
+    fn it_finds_where_walks_end_up() {
+        let walk = [Direction::NorthEast, Direction::SouthEast];
+        assert_eq!(displacement(&walk), (2, 0));
+        assert_eq!(displacement_with(&walk, Movement::Diagonal), Some((2, 0)));
+        assert_eq!(displacement_with(&walk, Movement::Hex), Some((2, -1)));
+        assert_eq!(displacement_with(&walk, Movement::Compass), None);
+    }
+
+    #[test]
+    fn it_does_not_validate_bad_walks() {
+        assert_eq!(
+            validate_walk(&['n', 's', 'n'], 10),
+            Err(WalkError::WrongLength {
+                minutes: 10,
+                steps: 3
+            })
+        );
+        assert_eq!(
+            validate_walk(&['n', 'e', 'n', 's'], 4),
+            Err(WalkError::NotHome {
+                displacement: (1, 1),
+                movement: Movement::Compass
+            })
+        );
+        assert_eq!(
+            validate_walk(&["n", "up"], 2),
+            Err(WalkError::UnknownDirection {
+                index: 1,
+                input: "up".to_owned()
+            })
+        );
+        assert_eq!(
+            validate_walk(&["n", "se", "w"], 3),
+            Err(WalkError::UnavailableDirection {
+                index: 1,
+                direction: Direction::SouthEast
+            })
+        );
+        assert_eq!(
+            WalkError::NotHome {
+                displacement: (1, -2),
+                movement: Movement::Compass
+            }
+            .to_string(),
+            "The walk ends 1 blocks east and -2 blocks north of where it started"
+        );
+        assert_eq!(
+            validate_walk_with(&["ne", "se"], 2, Movement::Hex).map_err(|e| e.to_string()),
+            Err("The walk ends 2 blocks away from where it started".to_owned())
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::env;
+use std::fs;
+use std::io::prelude::*;
+use std::io::Cursor;
+use std::process::{Command, Stdio};
+
+use ten_min_walk::*;
+
+fn conversation(input: &str) -> (Result<SessionState, CliError>, String) {
+    let mut output = vec![];
+    let result = run(Cursor::new(input), &mut output);
+    (result, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn it_walks_there_and_back() {
+    let (result, output) = conversation("2\nnorth\nsouth\n");
+
+    assert_eq!(result.unwrap(), SessionState::Home);
+    assert_eq!(
+        output,
+        "Hey I heard you want to take a walk!
+How long (in minutes) should we walk for?
+
+You have 2 minutes left
+Which direction would you like to go?
+So far you'r path has been: 
+Your current options are: North, South, East, West
+You chose: North
+
+You have 1 minutes left
+Which direction would you like to go?
+So far you'r path has been: North
+Your current options are: South
+You chose: South
+YAY we made it! Here is the path we took: North, South
+"
+    );
+}
+
+#[test]
+fn it_asks_again_for_unknown_directions() {
+    let (result, output) = conversation("4\nup\ne\n\nW\nn\ns\n");
+
+    assert_eq!(result.unwrap(), SessionState::Home);
+    assert_eq!(output.matches("You chose").count(), 4);
+    assert!(output.ends_with("the path we took: East, West, North, South\n"));
+}
+
+#[test]
+fn it_reads_diagonals_as_north_or_south_on_a_square_grid() {
+    let (result, output) = conversation("2\nne\nSouth-West\n");
+
+    assert_eq!(result.unwrap(), SessionState::Home);
+    assert!(output.ends_with("the path we took: North, South\n"));
+}
+
+#[test]
+fn it_walks_round_a_hex_triangle() {
+    let mut output = vec![];
+    let result = run_with(
+        Cursor::new("3\nnorth\neast\nse\nsouth-west\n"),
+        &mut output,
+        Movement::Hex,
+    );
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(result.unwrap(), SessionState::Home);
+    assert!(output.contains("Your current options are: South-East, South-West\n"));
+    assert!(output.ends_with("the path we took: North, South-East, South-West\n"));
+}
+
+#[test]
+fn it_gets_stranded() {
+    let (result, output) = conversation("2\nn\nn\n");
+
+    assert_eq!(result.unwrap(), SessionState::Stranded);
+    assert!(output.contains("OH NO! We won't be able to make it back in time!"));
+    assert!(output.ends_with("Here is what you tried this time: North, North\n"));
+}
+
+#[test]
+fn it_does_not_work_for_bad_input() {
+    let (result, _) = conversation("3\n");
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Walk time must be an even number or we will not be able to get back to where we started"
+    );
+
+    let (result, output) = conversation("4\nn\n");
+    assert!(matches!(result, Err(CliError::EndOfInput)));
+    assert!(output.ends_with("You chose: North\n\nYou have 3 minutes left\nWhich direction would you like to go?\nSo far you'r path has been: North\nYour current options are: North, South, East, West\n"));
+
+    let (result, _) = conversation("");
+    assert!(matches!(result, Err(CliError::EndOfInput)));
+}
+
+#[test]
+fn it_walks_round_a_map_from_a_file() {
+    let path = env::temp_dir().join("ten-min-walk-map-test.txt");
+    fs::write(&path, "H-.\n| 3\n.-.\n").unwrap();
+
+    let mut walker = Command::new(env!("CARGO_BIN_EXE_ten-min-walk"))
+        .arg("--map")
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    walker
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"6\nn\ne\ns\nw\nn\n")
+        .unwrap();
+    let result = walker.wait_with_output().unwrap();
+    fs::remove_file(&path).unwrap();
+    let output = String::from_utf8(result.stdout).unwrap();
+
+    assert!(result.status.success());
+    assert!(output.contains("Your current options are: South, East\n"));
+    assert!(output.contains("You chose: North\nThere is no street that way\n"));
+    assert!(output.contains("You have 2 minutes left"));
+    assert!(output.ends_with("the path we took: East, South, West, North\n"));
+}
+
+#[test]
+fn it_only_walks_maps_for_times_that_get_home() {
+    let map = WalkMap::parse("H-.\n| 3\n.-.").unwrap();
+
+    let mut output = vec![];
+    let result = run_on_map(Cursor::new("5\n"), &mut output, map);
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "There is no walk round the map that gets back to where we started in that time"
+    );
+}
@@ -0,0 +1,118 @@
+use crate::{Tile, TileSet};
+
+// SplitMix64, so a seed draws the same tiles on every platform and release
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+// Blanks in the bag are '?' until they are played
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TileBag {
+    tiles: Vec<Tile>,
+    random: Random,
+}
+
+impl TileBag {
+    pub fn new(tile_set: &TileSet, seed: u64) -> Self {
+        let letters = tile_set.tiles.iter().flat_map(|kind| {
+            (0..kind.count).map(move |_| Tile {
+                letter: kind.symbol,
+                blank: false,
+            })
+        });
+        let blanks = (0..tile_set.blanks).map(|_| Tile {
+            letter: '?',
+            blank: true,
+        });
+
+        let mut bag = TileBag {
+            tiles: letters.chain(blanks).collect(),
+            random: Random(seed),
+        };
+        bag.shuffle();
+        bag
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    // Fewer tiles come back when the bag runs low
+    pub fn draw(&mut self, count: usize) -> Vec<Tile> {
+        let count = count.min(self.tiles.len());
+        self.tiles.split_off(self.tiles.len() - count)
+    }
+
+    pub fn put_back(&mut self, tiles: Vec<Tile>) {
+        self.tiles.extend(tiles.into_iter().map(|tile| {
+            if tile.blank {
+                Tile {
+                    letter: '?',
+                    blank: true,
+                }
+            } else {
+                tile
+            }
+        }));
+        self.shuffle();
+    }
+
+    fn shuffle(&mut self) {
+        for i in (1..self.tiles.len()).rev() {
+            let j = self.random.below(i + 1);
+            self.tiles.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn it_works_for_seeded_bags() {
+        let tile_set = TileSet::english();
+        let mut bag = TileBag::new(&tile_set, 7);
+        assert_eq!(bag.len(), 100);
+
+        let rack = bag.draw(RACK_SIZE);
+        assert_eq!(rack, TileBag::new(&tile_set, 7).draw(RACK_SIZE));
+        assert_ne!(rack, TileBag::new(&tile_set, 8).draw(RACK_SIZE));
+        assert_eq!(bag.len(), 93);
+
+        let mut rest = bag.draw(200);
+        rest.extend(rack);
+        assert!(bag.is_empty());
+        assert_eq!(rest.iter().filter(|t| t.letter == 'e').count(), 12);
+        assert_eq!(rest.iter().filter(|t| t.blank).count(), 2);
+
+        bag.put_back(vec![Tile {
+            letter: 'e',
+            blank: true,
+        }]);
+        assert_eq!(
+            bag.draw(1),
+            vec![Tile {
+                letter: '?',
+                blank: true
+            }]
+        );
+    }
+}
@@ -0,0 +1,734 @@
+use std::fmt;
+
+use crate::{trimmed_lowercase, Letter, MoveError, ParseError, ScoreModifier, TileSet, Word};
+
+pub const BOARD_SIZE: usize = 15;
+pub const RACK_SIZE: usize = 7;
+pub const BINGO_BONUS: u32 = 50;
+
+// T is a triple word, D a double word, t a triple letter and d a double letter
+const LAYOUT: [&str; BOARD_SIZE] = [
+    "T..d...T...d..T",
+    ".D...t...t...D.",
+    "..D...d.d...D..",
+    "d..D...d...D..d",
+    "....D.....D....",
+    ".t...t...t...t.",
+    "..d...d.d...d..",
+    "T..d...D...d..T",
+    "..d...d.d...d..",
+    ".t...t...t...t.",
+    "....D.....D....",
+    "d..D...d...D..d",
+    "..D...d.d...D..",
+    ".D...t...t...D.",
+    "T..d...T...d..T",
+];
+
+pub(crate) const CENTER: Position = Position { row: 7, col: 7 };
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Premium {
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+impl Direction {
+    pub fn perpendicular(self) -> Self {
+        match self {
+            Direction::Across => Direction::Down,
+            Direction::Down => Direction::Across,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Self {
+        Position { row, col }
+    }
+
+    pub(crate) fn is_on_board(self) -> bool {
+        self.row < BOARD_SIZE && self.col < BOARD_SIZE
+    }
+
+    pub(crate) fn next(self, direction: Direction) -> Option<Self> {
+        let next = match direction {
+            Direction::Across => Position::new(self.row, self.col + 1),
+            Direction::Down => Position::new(self.row + 1, self.col),
+        };
+
+        Some(next).filter(|p| p.is_on_board())
+    }
+
+    pub(crate) fn previous(self, direction: Direction) -> Option<Self> {
+        match direction {
+            Direction::Across => Some(Position::new(self.row, self.col.checked_sub(1)?)),
+            Direction::Down => Some(Position::new(self.row.checked_sub(1)?, self.col)),
+        }
+    }
+
+    fn neighbours(self) -> Vec<Self> {
+        [Direction::Across, Direction::Down]
+            .iter()
+            .flat_map(|&d| vec![self.previous(d), self.next(d)])
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Tile {
+    pub letter: char,
+    pub blank: bool,
+}
+
+impl Tile {
+    // Uses the same notation as scrabble_score, so "he^llo" has a blank E.
+    // Error positions count characters from the start of the original string.
+    pub fn tiles_from_string(tiles: &str) -> Result<Vec<Self>, ParseError> {
+        Tile::tiles_from_string_with(tiles, &TileSet::english())
+    }
+
+    // A ^ after a multi-letter tile such as Spanish "ll^" makes the whole tile blank
+    pub fn tiles_from_string_with(
+        tiles: &str,
+        tile_set: &TileSet,
+    ) -> Result<Vec<Self>, ParseError> {
+        let (leading, chars) = trimmed_lowercase(tiles);
+        let mut result = vec![];
+        let mut i = 0;
+
+        while i < chars.len() {
+            let position = leading + i;
+            if chars[i] == '^' {
+                return Err(ParseError::MisplacedBlank { position });
+            }
+
+            // Tiles don't take multipliers, so * and ( are bad letters here
+            let rest: String = chars[i..].iter().collect();
+            let (letter, len) = tile_set.next_tile(&rest).ok_or(ParseError::BadLetter {
+                position,
+                character: chars[i],
+            })?;
+            i += rest[..len].chars().count();
+
+            let blank = chars.get(i) == Some(&'^');
+            if blank {
+                i += 1;
+            }
+            result.push(Tile { letter, blank });
+        }
+
+        Ok(result)
+    }
+
+    pub fn score(&self, tile_set: &TileSet) -> Result<u32, ParseError> {
+        Letter {
+            c: self.letter,
+            score_modifier: None,
+            blank_tile: self.blank,
+            position: 0,
+        }
+        .score(tile_set)
+    }
+
+    // The value of tiles such as those left on a rack. Error positions count
+    // the tiles.
+    pub fn tiles_value(tiles: &[Tile], tile_set: &TileSet) -> Result<u32, ParseError> {
+        tiles
+            .iter()
+            .enumerate()
+            .map(|(i, tile)| {
+                tile.score(tile_set).map_err(|_| ParseError::Unscorable {
+                    position: i,
+                    character: tile.letter,
+                })
+            })
+            .sum()
+    }
+
+    // Upper case, or lower case for a blank
+    pub fn name(&self, tile_set: &TileSet) -> String {
+        let name = tile_set.name(self.letter);
+        if self.blank {
+            name
+        } else {
+            name.to_uppercase()
+        }
+    }
+}
+
+// The tiles are laid from start along the direction, skipping over any
+// squares that already hold a tile
+#[derive(Debug, PartialEq, Clone)]
+pub struct Move {
+    pub start: Position,
+    pub direction: Direction,
+    pub tiles: Vec<Tile>,
+}
+
+impl Move {
+    pub fn new(start: Position, direction: Direction, tiles: Vec<Tile>) -> Self {
+        Move {
+            start,
+            direction,
+            tiles,
+        }
+    }
+}
+
+// Words are spelled in upper case with blanks in lower case
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WordScore {
+    pub word: String,
+    pub tiles: Vec<Tile>,
+    pub start: Position,
+    pub direction: Direction,
+    pub score: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveScore {
+    pub words: Vec<WordScore>,
+    pub bingo: bool,
+    pub total: u32,
+}
+
+struct Square {
+    position: Position,
+    tile: Tile,
+    is_new: bool,
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Board {
+    squares: [[Option<Tile>; BOARD_SIZE]; BOARD_SIZE],
+    tile_set: TileSet,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Board::default()
+    }
+
+    pub fn with_tile_set(tile_set: TileSet) -> Self {
+        Board {
+            tile_set,
+            ..Board::default()
+        }
+    }
+
+    pub fn tile_set(&self) -> &TileSet {
+        &self.tile_set
+    }
+
+    pub fn premium(position: Position) -> Option<Premium> {
+        match LAYOUT.get(position.row)?.as_bytes().get(position.col)? {
+            b'd' => Some(Premium::DoubleLetter),
+            b't' => Some(Premium::TripleLetter),
+            b'D' => Some(Premium::DoubleWord),
+            b'T' => Some(Premium::TripleWord),
+            _ => None,
+        }
+    }
+
+    pub fn tile(&self, position: Position) -> Option<Tile> {
+        *self.squares.get(position.row)?.get(position.col)?
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.squares.iter().flatten().all(Option::is_none)
+    }
+
+    // Scores the main word along the move's direction followed by every
+    // cross-word the new tiles form, in the order the tiles were placed
+    pub fn score_move(&self, mv: &Move) -> Result<MoveScore, MoveError> {
+        let placements = self.placements(mv)?;
+        let main_word = self.word_through(&placements, placements[0].0, mv.direction);
+        let cross_words = placements.iter().map(|(position, _)| {
+            self.word_through(&placements, *position, mv.direction.perpendicular())
+        });
+
+        let words = Some(main_word)
+            .into_iter()
+            .chain(cross_words)
+            .filter(|squares| squares.len() > 1)
+            .map(|squares| {
+                Ok(WordScore {
+                    word: squares
+                        .iter()
+                        .map(|square| square.tile.name(&self.tile_set))
+                        .collect(),
+                    tiles: squares.iter().map(|square| square.tile).collect(),
+                    start: squares[0].position,
+                    direction: if squares[0].position.row == squares[1].position.row {
+                        Direction::Across
+                    } else {
+                        Direction::Down
+                    },
+                    score: Board::word_from_squares(&squares).score(&self.tile_set)?,
+                })
+            })
+            .collect::<Result<Vec<WordScore>, ParseError>>()?;
+        if words.is_empty() {
+            return Err(MoveError::TooShort);
+        }
+
+        // Playing a whole rack earns the bingo bonus on top of the words
+        let bingo = placements.len() == RACK_SIZE;
+        let bonus = if bingo { BINGO_BONUS } else { 0 };
+
+        Ok(MoveScore {
+            total: words.iter().map(|w| w.score).sum::<u32>() + bonus,
+            words,
+            bingo,
+        })
+    }
+
+    // Places the move's tiles and returns its score
+    pub fn place(&mut self, mv: &Move) -> Result<MoveScore, MoveError> {
+        let score = self.score_move(mv)?;
+        for (position, tile) in self.placements(mv)? {
+            self.squares[position.row][position.col] = Some(tile);
+        }
+
+        Ok(score)
+    }
+
+    fn placements(&self, mv: &Move) -> Result<Vec<(Position, Tile)>, MoveError> {
+        if mv.tiles.is_empty() {
+            return Err(MoveError::NoTiles);
+        }
+        if !mv.start.is_on_board() {
+            return Err(MoveError::StartsOffBoard);
+        }
+
+        let mut placements = vec![];
+        let mut position = Some(mv.start);
+        for (i, tile) in mv.tiles.iter().enumerate() {
+            // A blank still has to stand for one of the tile set's letters
+            if self.tile_set.value(tile.letter).is_none() {
+                return Err(MoveError::BadTile(ParseError::BadLetter {
+                    position: i,
+                    character: tile.letter,
+                }));
+            }
+            while let Some(p) = position.filter(|p| self.tile(*p).is_some()) {
+                position = p.next(mv.direction);
+            }
+            let p = position.ok_or(MoveError::RunsOffBoard)?;
+            placements.push((p, *tile));
+            position = p.next(mv.direction);
+        }
+
+        let touches_board = if self.is_empty() {
+            placements.iter().any(|(p, _)| *p == CENTER)
+        } else {
+            placements.iter().any(|(p, _)| {
+                p.neighbours()
+                    .iter()
+                    .any(|neighbour| self.tile(*neighbour).is_some())
+            })
+        };
+        if !touches_board {
+            return Err(if self.is_empty() {
+                MoveError::NotOnCenter
+            } else {
+                MoveError::NotConnected
+            });
+        }
+
+        Ok(placements)
+    }
+
+    // The run of tiles through position along direction, counting both the
+    // tiles already on the board and the ones being placed
+    fn word_through(
+        &self,
+        placements: &[(Position, Tile)],
+        position: Position,
+        direction: Direction,
+    ) -> Vec<Square> {
+        let square = |position: Position| {
+            placements
+                .iter()
+                .find(|(p, _)| *p == position)
+                .map(|(_, tile)| Square {
+                    position,
+                    tile: *tile,
+                    is_new: true,
+                })
+                .or_else(|| {
+                    self.tile(position).map(|tile| Square {
+                        position,
+                        tile,
+                        is_new: false,
+                    })
+                })
+        };
+
+        let mut start = position;
+        while let Some(previous) = start.previous(direction).filter(|p| square(*p).is_some()) {
+            start = previous;
+        }
+
+        let mut squares = vec![];
+        let mut position = Some(start);
+        while let Some(s) = position.and_then(square) {
+            position = s.position.next(direction);
+            squares.push(s);
+        }
+
+        squares
+    }
+
+    // Premium squares only count for tiles placed by this move
+    fn word_from_squares(squares: &[Square]) -> Word {
+        let mut modifiers = vec![];
+        let letters = squares
+            .iter()
+            .enumerate()
+            .map(|(i, square)| {
+                let premium = Some(square.position)
+                    .filter(|_| square.is_new)
+                    .and_then(Board::premium);
+                let score_modifier = match premium {
+                    Some(Premium::DoubleLetter) => Some(ScoreModifier::Double),
+                    Some(Premium::TripleLetter) => Some(ScoreModifier::Triple),
+                    Some(Premium::DoubleWord) => {
+                        modifiers.push(ScoreModifier::Double);
+                        None
+                    }
+                    Some(Premium::TripleWord) => {
+                        modifiers.push(ScoreModifier::Triple);
+                        None
+                    }
+                    None => None,
+                };
+
+                Letter {
+                    c: square.tile.letter,
+                    score_modifier,
+                    blank_tile: square.tile.blank,
+                    position: i,
+                }
+            })
+            .collect();
+
+        Word { letters, modifiers }
+    }
+}
+
+// Tiles are shown in upper case, blanks in lower case and empty squares with
+// their premium from the layout
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (row, layout) in self.squares.iter().zip(LAYOUT.iter()) {
+            let line: String = row
+                .iter()
+                .zip(layout.chars())
+                .map(|(tile, premium)| match tile {
+                    Some(tile) => tile.name(&self.tile_set),
+                    None => premium.to_string(),
+                })
+                .collect();
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn play(
+        board: &mut Board,
+        row: usize,
+        col: usize,
+        direction: Direction,
+        tiles: &str,
+    ) -> Result<u32, MoveError> {
+        let mv = Move::new(
+            Position::new(row, col),
+            direction,
+            Tile::tiles_from_string(tiles)?,
+        );
+
+        Ok(board.place(&mv)?.total)
+    }
+
+    #[test]
+    fn it_has_the_standard_premium_layout() {
+        let count = |premium| {
+            (0..BOARD_SIZE)
+                .flat_map(|row| (0..BOARD_SIZE).map(move |col| Position::new(row, col)))
+                .filter(|p| Board::premium(*p) == Some(premium))
+                .count()
+        };
+
+        assert_eq!(count(Premium::TripleWord), 8);
+        assert_eq!(count(Premium::DoubleWord), 17);
+        assert_eq!(count(Premium::TripleLetter), 12);
+        assert_eq!(count(Premium::DoubleLetter), 24);
+        assert_eq!(
+            Board::premium(Position::new(7, 7)),
+            Some(Premium::DoubleWord)
+        );
+        assert_eq!(Board::premium(Position::new(15, 0)), None);
+    }
+
+    #[test]
+    fn it_works_for_an_opening_move() -> Result<(), MoveError> {
+        let mut board = Board::new();
+
+        // H on a double letter and O on the center star
+        assert_eq!(play(&mut board, 7, 3, Direction::Across, "hello")?, 24);
+        assert_eq!(
+            board.tile(Position::new(7, 3)),
+            Some(Tile {
+                letter: 'h',
+                blank: false
+            })
+        );
+        assert!(board.to_string().starts_with("T..d...T...d..T\n"));
+        assert!(board.to_string().contains("\nT..HELLO...d..T\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_only_for_premiums_under_new_tiles() -> Result<(), MoveError> {
+        let mut board = Board::new();
+        play(&mut board, 7, 3, Direction::Across, "hello")?;
+
+        // SNOW runs through the O on the center star without doubling again
+        assert_eq!(play(&mut board, 5, 7, Direction::Down, "snw")?, 7);
+        assert_eq!(
+            board.tile(Position::new(8, 7)),
+            Some(Tile {
+                letter: 'w',
+                blank: false
+            })
+        );
+
+        // HELLOS picks up nothing new for the existing letters either
+        assert_eq!(play(&mut board, 7, 3, Direction::Across, "s^")?, 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_multi_letter_tiles() -> Result<(), MoveError> {
+        let spanish = TileSet::spanish();
+        let mut board = Board::with_tile_set(spanish.clone());
+        let mv = Move::new(
+            Position::new(7, 4),
+            Direction::Across,
+            Tile::tiles_from_string_with("chorro", &spanish)?,
+        );
+        let score = board.place(&mv)?;
+
+        assert_eq!(mv.tiles.len(), 4);
+        assert_eq!(score.words[0].word, "CHORRO");
+        assert_eq!(score.total, (5 + 1 + 8 + 1) * 2);
+        assert!(board.to_string().contains("\nT..dCHORRO...d..T\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_cross_words() -> Result<(), MoveError> {
+        let mut board = Board::new();
+        play(&mut board, 7, 3, Direction::Across, "hello")?;
+
+        // AX under HE also makes HA and EX
+        let mv = Move::new(
+            Position::new(8, 3),
+            Direction::Across,
+            Tile::tiles_from_string("ax")?,
+        );
+        let score = board.score_move(&mv)?;
+        let words: Vec<(&str, Position, Direction, u32)> = score
+            .words
+            .iter()
+            .map(|w| (w.word.as_str(), w.start, w.direction, w.score))
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                ("AX", Position::new(8, 3), Direction::Across, 9),
+                ("HA", Position::new(7, 3), Direction::Down, 5),
+                ("EX", Position::new(7, 4), Direction::Down, 9),
+            ]
+        );
+        assert_eq!(score.total, 23);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_a_single_tile_forming_only_a_cross_word() -> Result<(), MoveError> {
+        let mut board = Board::new();
+        play(&mut board, 7, 3, Direction::Across, "hello")?;
+
+        let score = board.place(&Move::new(
+            Position::new(7, 8),
+            Direction::Down,
+            Tile::tiles_from_string("s")?,
+        ))?;
+        assert_eq!(score.words.len(), 1);
+        assert_eq!(score.words[0].word, "HELLOS");
+        assert_eq!(score.total, 9);
+
+        // A premium under a new tile counts in both words it belongs to
+        assert_eq!(
+            play(&mut board, 6, 6, Direction::Across, "zo")?,
+            21 + 21 + 2
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_bingos() -> Result<(), MoveError> {
+        let mut board = Board::new();
+        let mv = Move::new(
+            Position::new(7, 1),
+            Direction::Across,
+            Tile::tiles_from_string("letters")?,
+        );
+        let score = board.place(&mv)?;
+
+        assert!(score.bingo);
+        assert_eq!(score.words[0].score, 16);
+        assert_eq!(score.total, 16 + BINGO_BONUS);
+        assert!(
+            !board
+                .score_move(&Move::new(
+                    Position::new(8, 1),
+                    Direction::Across,
+                    Tile::tiles_from_string("a")?,
+                ))?
+                .bingo
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_for_illegal_placements() -> Result<(), MoveError> {
+        let mut board = Board::new();
+        assert_eq!(
+            play(&mut board, 0, 0, Direction::Across, "hello"),
+            Err(MoveError::NotOnCenter)
+        );
+        assert_eq!(
+            play(&mut board, 7, 7, Direction::Across, "a"),
+            Err(MoveError::TooShort)
+        );
+        assert_eq!(
+            play(&mut board, 7, 12, Direction::Across, "hello"),
+            Err(MoveError::RunsOffBoard)
+        );
+
+        play(&mut board, 7, 3, Direction::Across, "hello")?;
+        assert_eq!(
+            play(&mut board, 0, 0, Direction::Down, "hello"),
+            Err(MoveError::NotConnected)
+        );
+        assert_eq!(
+            board.place(&Move::new(Position::new(7, 7), Direction::Down, vec![])),
+            Err(MoveError::NoTiles)
+        );
+        let unknown = Tile {
+            letter: '1',
+            blank: false,
+        };
+        assert_eq!(
+            board.place(&Move::new(
+                Position::new(6, 3),
+                Direction::Down,
+                vec![unknown]
+            )),
+            Err(MoveError::BadTile(ParseError::BadLetter {
+                position: 0,
+                character: '1'
+            }))
+        );
+        let blank = Tile {
+            letter: '1',
+            blank: true,
+        };
+        assert_eq!(
+            board.place(&Move::new(
+                Position::new(6, 3),
+                Direction::Down,
+                vec![Tile::tiles_from_string("a")?[0], blank]
+            )),
+            Err(MoveError::BadTile(ParseError::BadLetter {
+                position: 1,
+                character: '1'
+            }))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_for_bad_tiles() {
+        assert_eq!(
+            Tile::tiles_from_string("he*llo"),
+            Err(ParseError::BadLetter {
+                position: 2,
+                character: '*'
+            })
+        );
+        assert_eq!(
+            Tile::tiles_from_string(" ^hello"),
+            Err(ParseError::MisplacedBlank { position: 1 })
+        );
+        assert_eq!(
+            Tile::tiles_from_string_with("ll^ñu", &TileSet::english()),
+            Err(ParseError::BadLetter {
+                position: 3,
+                character: 'ñ'
+            })
+        );
+        assert_eq!(
+            Tile::tiles_from_string_with("ll^ñu", &TileSet::spanish()).map(|tiles| tiles.len()),
+            Ok(3)
+        );
+        let enye = Tile {
+            letter: 'ñ',
+            blank: false,
+        };
+        assert_eq!(
+            enye.score(&TileSet::english()),
+            Err(ParseError::Unscorable {
+                position: 0,
+                character: 'ñ'
+            })
+        );
+        assert_eq!(
+            enye.score(&TileSet::english()).map_err(|e| e.to_string()),
+            Err("'ñ' at position 0 is not a tile in the tile set".to_owned())
+        );
+        assert_eq!(enye.score(&TileSet::spanish()), Ok(8));
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Board, Move, MoveError, MoveScore, ParseError, Tile, TileSet, Word};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DictionaryError {
+    Io(String),
+    InvalidEntry { line_number: usize, line: String },
+    NotAWord(String),
+    IllegalMove(MoveError),
+    Parse(ParseError),
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DictionaryError::Io(e) => write!(f, "Could not read the word list: {}", e),
+            DictionaryError::InvalidEntry { line_number, line } => {
+                write!(f, "Line {}: not a word: {}", line_number, line)
+            }
+            DictionaryError::NotAWord(word) => write!(f, "{} is not in the dictionary", word),
+            DictionaryError::IllegalMove(e) => write!(f, "{}", e),
+            DictionaryError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+impl From<io::Error> for DictionaryError {
+    fn from(e: io::Error) -> Self {
+        DictionaryError::Io(e.to_string())
+    }
+}
+
+impl From<MoveError> for DictionaryError {
+    fn from(e: MoveError) -> Self {
+        DictionaryError::IllegalMove(e)
+    }
+}
+
+impl From<ParseError> for DictionaryError {
+    fn from(e: ParseError) -> Self {
+        DictionaryError::Parse(e)
+    }
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub(crate) struct Node {
+    pub(crate) children: BTreeMap<char, Node>,
+    pub(crate) is_word: bool,
+}
+
+// A trie of words, stored as the symbols of the tile set's tiles
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct Dictionary {
+    root: Node,
+    len: usize,
+    tile_set: TileSet,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Dictionary::default()
+    }
+
+    pub fn with_tile_set(tile_set: TileSet) -> Self {
+        Dictionary {
+            tile_set,
+            ..Dictionary::default()
+        }
+    }
+
+    // One word per line as in TWL or SOWPODS lists. Anything after the word
+    // on a line is ignored, as are blank lines and lines starting with #.
+    pub fn parse(word_list: &str) -> Result<Self, DictionaryError> {
+        Dictionary::parse_with(word_list, TileSet::english())
+    }
+
+    // Words are split into the tile set's tiles, so a Spanish list can
+    // spell CH as "ch"
+    pub fn parse_with(word_list: &str, tile_set: TileSet) -> Result<Self, DictionaryError> {
+        let mut dictionary = Dictionary::with_tile_set(tile_set);
+
+        for (i, line) in word_list.lines().enumerate() {
+            let word = match line.split_whitespace().next() {
+                Some(word) if !word.starts_with('#') => word,
+                _ => continue,
+            };
+            if !dictionary.insert(word) && !dictionary.contains(word) {
+                return Err(DictionaryError::InvalidEntry {
+                    line_number: i + 1,
+                    line: line.to_owned(),
+                });
+            }
+        }
+
+        Ok(dictionary)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DictionaryError> {
+        Dictionary::from_file_with(path, TileSet::english())
+    }
+
+    pub fn from_file_with<P: AsRef<Path>>(
+        path: P,
+        tile_set: TileSet,
+    ) -> Result<Self, DictionaryError> {
+        Dictionary::parse_with(&fs::read_to_string(path)?, tile_set)
+    }
+
+    pub fn tile_set(&self) -> &TileSet {
+        &self.tile_set
+    }
+
+    // Returns false if the word was already there or can't be spelled with
+    // the tile set
+    pub fn insert(&mut self, word: &str) -> bool {
+        let symbols = match self.tile_set.tokenize(word) {
+            Some(symbols) if !symbols.is_empty() => symbols,
+            _ => return false,
+        };
+        let node = symbols.into_iter().fold(&mut self.root, |node, c| {
+            node.children.entry(c).or_default()
+        });
+        if node.is_word {
+            return false;
+        }
+
+        node.is_word = true;
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.tile_set
+            .tokenize(word)
+            .is_some_and(|symbols| self.contains_symbols(&symbols))
+    }
+
+    pub(crate) fn contains_symbols(&self, symbols: &[char]) -> bool {
+        self.node(symbols).is_some_and(|node| node.is_word)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn root(&self) -> &Node {
+        &self.root
+    }
+
+    fn node(&self, prefix: &[char]) -> Option<&Node> {
+        prefix
+            .iter()
+            .try_fold(&self.root, |node, c| node.children.get(c))
+    }
+
+    // Scores a word written for scrabble_score, but only if it's in the
+    // dictionary. Blank tiles are checked as the letter they stand for.
+    pub fn score(&self, word: &str) -> Result<u32, DictionaryError> {
+        let word = Word::from_string(word, &self.tile_set)?;
+        let symbols = word.symbols();
+        if !self.contains_symbols(&symbols) {
+            return Err(DictionaryError::NotAWord(
+                self.tile_set.spell(&symbols).to_uppercase(),
+            ));
+        }
+
+        Ok(word.score(&self.tile_set)?)
+    }
+}
+
+impl Board {
+    // Like score_move, but every word formed must be in the dictionary
+    pub fn score_move_with(
+        &self,
+        mv: &Move,
+        dictionary: &Dictionary,
+    ) -> Result<MoveScore, DictionaryError> {
+        let score = self.score_move(mv)?;
+        let is_word = |tiles: &[Tile]| {
+            let symbols: Vec<char> = tiles.iter().map(|tile| tile.letter).collect();
+            dictionary.contains_symbols(&symbols)
+        };
+
+        match score.words.iter().find(|w| !is_word(&w.tiles)) {
+            Some(invalid) => Err(DictionaryError::NotAWord(invalid.word.clone())),
+            None => Ok(score),
+        }
+    }
+
+    pub fn place_with(
+        &mut self,
+        mv: &Move,
+        dictionary: &Dictionary,
+    ) -> Result<MoveScore, DictionaryError> {
+        self.score_move_with(mv, dictionary)?;
+
+        Ok(self.place(mv)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use crate::*;
+
+    const WORD_LIST: &str = "# A tiny word list
+HELLO
+quiz  a test of knowledge
+ax
+
+ha
+ex
+";
+
+    #[test]
+    fn it_works_for_a_word_list() -> Result<(), DictionaryError> {
+        let dictionary = Dictionary::parse(WORD_LIST)?;
+
+        assert_eq!(dictionary.len(), 5);
+        assert!(dictionary.contains("hello"));
+        assert!(dictionary.contains("QUIZ"));
+        assert!(!dictionary.contains("hell"));
+        assert!(!dictionary.contains("knowledge"));
+        assert!(!Dictionary::new().contains(""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_a_word_list_file() -> Result<(), DictionaryError> {
+        let path = env::temp_dir().join("scrabble-score-dictionary-test.txt");
+        fs::write(&path, WORD_LIST).unwrap();
+        let dictionary = Dictionary::from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(dictionary?, Dictionary::parse(WORD_LIST)?);
+        assert!(matches!(
+            Dictionary::from_file(env::temp_dir().join("scrabble-score-missing.txt")),
+            Err(DictionaryError::Io(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_other_tile_sets() -> Result<(), DictionaryError> {
+        let dictionary = Dictionary::parse_with("CHORRO\nniño", TileSet::spanish())?;
+
+        assert!(dictionary.contains("chorro"));
+        assert!(dictionary.contains("NIÑO"));
+        assert_eq!(dictionary.score("chorr^o")?, 7);
+        assert_eq!(
+            Dictionary::parse("niño"),
+            Err(DictionaryError::InvalidEntry {
+                line_number: 1,
+                line: "niño".to_owned()
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_for_bad_entries() {
+        assert_eq!(
+            Dictionary::parse("hello\nbad-word"),
+            Err(DictionaryError::InvalidEntry {
+                line_number: 2,
+                line: "bad-word".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn it_works_for_scoring_dictionary_words() -> Result<(), DictionaryError> {
+        let dictionary = Dictionary::parse(WORD_LIST)?;
+
+        assert_eq!(dictionary.score("quiz(d)")?, 44);
+        // The blank stands for the Z, so this is still QUIZ
+        assert_eq!(dictionary.score("qu*iz^")?, 13);
+        assert_eq!(
+            dictionary.score("zzqx"),
+            Err(DictionaryError::NotAWord("ZZQX".to_owned()))
+        );
+        assert_eq!(
+            dictionary.score("qu1z"),
+            Err(DictionaryError::Parse(ParseError::BadLetter {
+                position: 2,
+                character: '1'
+            }))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_moves_on_the_board() -> Result<(), DictionaryError> {
+        let dictionary = Dictionary::parse(WORD_LIST)?;
+        let mut board = Board::new();
+        let mv = |row, col, tiles| -> Result<Move, DictionaryError> {
+            Ok(Move::new(
+                Position::new(row, col),
+                Direction::Across,
+                Tile::tiles_from_string(tiles)?,
+            ))
+        };
+
+        board.place_with(&mv(7, 3, "hel^lo")?, &dictionary)?;
+        assert_eq!(
+            board.place_with(&mv(8, 3, "ax")?, &dictionary)?.total,
+            9 + 5 + 9
+        );
+        assert_eq!(
+            board.place_with(&mv(9, 3, "xx")?, &dictionary),
+            Err(DictionaryError::NotAWord("XX".to_owned()))
+        );
+        assert_eq!(board.tile(Position::new(9, 3)), None);
+
+        Ok(())
+    }
+}
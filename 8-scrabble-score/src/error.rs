@@ -0,0 +1,99 @@
+use std::fmt;
+
+// Positions count characters from the start of the word as it was given
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    BadLetter { position: usize, character: char },
+    TooManyMultipliers { position: usize },
+    MisplacedMultiplier { position: usize },
+    MisplacedBlank { position: usize },
+    BadWordModifier { position: usize },
+    StrayWordModifier { position: usize },
+    // A letter the tile set has no value for
+    Unscorable { position: usize, character: char },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::BadLetter {
+                position,
+                character,
+            } => write!(
+                f,
+                "'{}' at position {} is not a letter",
+                character, position
+            ),
+            ParseError::TooManyMultipliers { position } => {
+                write!(f, "Too many letter multipliers at position {}", position)
+            }
+            ParseError::MisplacedMultiplier { position } => write!(
+                f,
+                "The letter multiplier at position {} does not follow a letter",
+                position
+            ),
+            ParseError::MisplacedBlank { position } => write!(
+                f,
+                "The blank at position {} does not follow a letter or its multiplier",
+                position
+            ),
+            ParseError::BadWordModifier { position } => write!(
+                f,
+                "The word modifier at position {} should be (d) or (t)",
+                position
+            ),
+            ParseError::StrayWordModifier { position } => write!(
+                f,
+                "The word modifier at position {} is not at the end of the word",
+                position
+            ),
+            ParseError::Unscorable {
+                position,
+                character,
+            } => write!(
+                f,
+                "'{}' at position {} is not a tile in the tile set",
+                character, position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Why a move can't be placed on the board
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MoveError {
+    NoTiles,
+    StartsOffBoard,
+    RunsOffBoard,
+    NotOnCenter,
+    NotConnected,
+    TooShort,
+    // Positions count the move's tiles
+    BadTile(ParseError),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::NoTiles => write!(f, "A move must place at least one tile"),
+            MoveError::StartsOffBoard => write!(f, "The move starts off the board"),
+            MoveError::RunsOffBoard => write!(f, "The move runs off the board"),
+            MoveError::NotOnCenter => write!(f, "The first move must cover the center square"),
+            MoveError::NotConnected => {
+                write!(f, "The move must connect to tiles already on the board")
+            }
+            MoveError::TooShort => write!(f, "A move must form a word of at least two letters"),
+            MoveError::BadTile(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+impl From<ParseError> for MoveError {
+    fn from(e: ParseError) -> Self {
+        MoveError::BadTile(e)
+    }
+}
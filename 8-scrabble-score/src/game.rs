@@ -0,0 +1,385 @@
+use std::fmt;
+
+use crate::{
+    Board, Dictionary, DictionaryError, Move, MoveError, MoveScore, ParseError, Rack, Scoresheet,
+    ScoresheetEntry, ScoresheetError, Tile, TileBag, TileSet, Turn, RACK_SIZE,
+};
+
+// The game also ends once this many turns in a row have been passes or exchanges
+pub const SCORELESS_TURN_LIMIT: usize = 6;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum GameError {
+    NoPlayers,
+    IllegalMove(MoveError),
+    NotAWord(String),
+    Parse(ParseError),
+    // The dictionary itself could not be used
+    Dictionary(DictionaryError),
+    Scoresheet(ScoresheetError),
+    NotOnRack,
+    BagTooSmall,
+    GameOver,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::NoPlayers => write!(f, "A game needs at least one player"),
+            GameError::IllegalMove(e) => write!(f, "{}", e),
+            GameError::NotAWord(word) => write!(f, "{} is not in the dictionary", word),
+            GameError::Parse(e) => write!(f, "{}", e),
+            GameError::Dictionary(e) => write!(f, "{}", e),
+            GameError::Scoresheet(e) => write!(f, "{}", e),
+            GameError::NotOnRack => write!(f, "Those tiles are not on the player's rack"),
+            GameError::BagTooSmall => write!(f, "There are too few tiles left to exchange"),
+            GameError::GameOver => write!(f, "The game is over"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<MoveError> for GameError {
+    fn from(e: MoveError) -> Self {
+        GameError::IllegalMove(e)
+    }
+}
+
+impl From<ParseError> for GameError {
+    fn from(e: ParseError) -> Self {
+        GameError::Parse(e)
+    }
+}
+
+impl From<DictionaryError> for GameError {
+    fn from(e: DictionaryError) -> Self {
+        match e {
+            DictionaryError::NotAWord(word) => GameError::NotAWord(word),
+            DictionaryError::IllegalMove(e) => GameError::IllegalMove(e),
+            DictionaryError::Parse(e) => GameError::Parse(e),
+            DictionaryError::Io(_) | DictionaryError::InvalidEntry { .. } => {
+                GameError::Dictionary(e)
+            }
+        }
+    }
+}
+
+// A turn as it was taken, with the rack the player held beforehand
+#[derive(Debug, PartialEq, Clone)]
+pub struct TurnRecord {
+    pub rack: Rack,
+    pub entry: ScoresheetEntry,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    board: Board,
+    bag: TileBag,
+    racks: Vec<Rack>,
+    scores: Vec<i32>,
+    current: usize,
+    scoreless_turns: usize,
+    history: Vec<TurnRecord>,
+    dictionary: Option<Dictionary>,
+}
+
+impl Game {
+    // Each player draws a full rack, in turn order, from a bag shuffled by the seed
+    pub fn new(players: usize, tile_set: TileSet, seed: u64) -> Result<Self, GameError> {
+        if players == 0 {
+            return Err(GameError::NoPlayers);
+        }
+
+        let mut bag = TileBag::new(&tile_set, seed);
+        let racks = (0..players)
+            .map(|_| {
+                let mut rack = Rack::with_tile_set(tile_set.clone());
+                for tile in bag.draw(RACK_SIZE) {
+                    rack.add(tile);
+                }
+                rack
+            })
+            .collect();
+
+        Ok(Game {
+            board: Board::with_tile_set(tile_set),
+            bag,
+            racks,
+            scores: vec![0; players],
+            current: 0,
+            scoreless_turns: 0,
+            history: vec![],
+            dictionary: None,
+        })
+    }
+
+    // Plays must then only form words in the dictionary
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn bag(&self) -> &TileBag {
+        &self.bag
+    }
+
+    pub fn rack(&self, player: usize) -> Option<&Rack> {
+        self.racks.get(player)
+    }
+
+    pub fn scores(&self) -> &[i32] {
+        &self.scores
+    }
+
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    pub fn history(&self) -> &[TurnRecord] {
+        &self.history
+    }
+
+    // Over once a player uses their last tile with the bag empty, or after
+    // too many scoreless turns in a row
+    pub fn is_over(&self) -> bool {
+        self.scoreless_turns >= SCORELESS_TURN_LIMIT
+            || (self.bag.is_empty() && self.racks.iter().any(Rack::is_empty))
+    }
+
+    pub fn play(&mut self, mv: Move) -> Result<&TurnRecord, GameError> {
+        self.check_not_over()?;
+        let mut rack = self.racks[self.current].clone();
+        if !mv.tiles.iter().all(|&tile| rack.remove(tile)) {
+            return Err(GameError::NotOnRack);
+        }
+
+        let score = match &self.dictionary {
+            Some(dictionary) => self.board.place_with(&mv, dictionary)?,
+            None => self.board.place(&mv)?,
+        };
+        for tile in self.bag.draw(mv.tiles.len()) {
+            rack.add(tile);
+        }
+        self.scoreless_turns = 0;
+
+        Ok(self.finish_turn(rack, Turn::Play(mv), Some(score)))
+    }
+
+    // Blanks can be given as any letter
+    pub fn exchange(&mut self, tiles: Vec<Tile>) -> Result<&TurnRecord, GameError> {
+        self.check_not_over()?;
+        if self.bag.len() < RACK_SIZE {
+            return Err(GameError::BagTooSmall);
+        }
+        let mut rack = self.racks[self.current].clone();
+        if tiles.is_empty() || !tiles.iter().all(|&tile| rack.remove(tile)) {
+            return Err(GameError::NotOnRack);
+        }
+
+        for tile in self.bag.draw(tiles.len()) {
+            rack.add(tile);
+        }
+        self.bag.put_back(tiles.clone());
+        self.scoreless_turns += 1;
+
+        Ok(self.finish_turn(rack, Turn::Exchange(tiles), None))
+    }
+
+    pub fn pass(&mut self) -> Result<&TurnRecord, GameError> {
+        self.check_not_over()?;
+        let rack = self.racks[self.current].clone();
+        self.scoreless_turns += 1;
+
+        Ok(self.finish_turn(rack, Turn::Pass, None))
+    }
+
+    // The turns so far, with the end-game rack adjustments once the game is over
+    pub fn scoresheet(&self) -> Result<Scoresheet, GameError> {
+        let turns: Vec<Turn> = self.history.iter().map(|r| r.entry.turn.clone()).collect();
+        let racks: Vec<Vec<Tile>> = if self.is_over() {
+            self.racks.iter().map(Rack::tiles).collect()
+        } else {
+            vec![vec![]; self.racks.len()]
+        };
+
+        let mut scoresheet = Scoresheet::with_tile_set(
+            self.board.tile_set().clone(),
+            self.racks.len(),
+            &turns,
+            &racks,
+        )
+        .map_err(GameError::Scoresheet)?;
+        if !self.is_over() {
+            scoresheet.adjustments.clear();
+            scoresheet.final_scores = self.scores.clone();
+        }
+
+        Ok(scoresheet)
+    }
+
+    fn check_not_over(&self) -> Result<(), GameError> {
+        if self.is_over() {
+            Err(GameError::GameOver)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn finish_turn(&mut self, rack: Rack, turn: Turn, score: Option<MoveScore>) -> &TurnRecord {
+        let player = self.current;
+        let points = score.as_ref().map_or(0, |s| s.total as i32);
+        self.scores[player] += points;
+
+        let before = std::mem::replace(&mut self.racks[player], rack);
+        self.history.push(TurnRecord {
+            rack: before,
+            entry: ScoresheetEntry {
+                player,
+                turn,
+                score,
+                points,
+                total: self.scores[player],
+            },
+        });
+        self.current = (self.current + 1) % self.racks.len();
+
+        self.history.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const WORD_LIST: &str = "aa ab ad ae ag ah ai al am an ar as at aw ax ay ba be bi bo by da de do ed ef eh el em en er es et ex fa fe go ha he hi hm ho id if in is it jo ka ki la li lo ma me mi mm mo mu my na ne no nu od oe of oh oi om on op or os ow ox oy pa pe pi qi re sh si so ta ti to uh um un up us ut we wo xi xu ya ye yo za";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(&WORD_LIST.replace(' ', "\n")).unwrap()
+    }
+
+    // Plays the best move each turn, exchanging or passing when there isn't one
+    fn autoplay(seed: u64) -> Result<Game, GameError> {
+        let dictionary = dictionary();
+        let mut game = Game::new(2, TileSet::english(), seed)?.with_dictionary(dictionary.clone());
+
+        while !game.is_over() {
+            let rack = game.rack(game.current_player()).unwrap().clone();
+            let best = best_moves(Some(game.board()), &rack, &dictionary, 1);
+            match best.into_iter().next() {
+                Some(ranked) => game.play(ranked.mv)?,
+                None if game.bag().len() >= RACK_SIZE => game.exchange(rack.tiles())?,
+                None => game.pass()?,
+            };
+        }
+
+        Ok(game)
+    }
+
+    #[test]
+    fn it_works_for_whole_games() -> Result<(), GameError> {
+        let mut game = autoplay(2019)?;
+        let scoresheet = game.scoresheet()?;
+
+        assert!(game.is_over());
+        assert_eq!(game.history().len(), scoresheet.entries.len());
+        assert_eq!(scoresheet.adjustments.len(), 2);
+        for (record, entry) in game.history().iter().zip(&scoresheet.entries) {
+            assert_eq!(&record.entry, entry);
+        }
+        let mv = Move::new(Position::new(7, 7), Direction::Across, vec![]);
+        assert_eq!(game.play(mv).err(), Some(GameError::GameOver));
+
+        // The same seed replays the same game
+        let replay = autoplay(2019)?;
+        assert_eq!(replay.history(), game.history());
+        assert_eq!(replay.scoresheet()?.final_scores, scoresheet.final_scores);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_exchanges_and_passes() -> Result<(), GameError> {
+        let mut game = Game::new(3, TileSet::english(), 1)?;
+        let rack = game.rack(0).unwrap().clone();
+        assert_eq!(rack.len(), RACK_SIZE);
+        assert_eq!(game.bag().len(), 100 - 3 * RACK_SIZE);
+
+        let record = game.exchange(rack.tiles()[..2].to_vec())?.clone();
+        assert_eq!(record.rack, rack);
+        assert_eq!(record.entry.points, 0);
+        assert_eq!(game.rack(0).unwrap().len(), RACK_SIZE);
+        assert_eq!(game.bag().len(), 100 - 3 * RACK_SIZE);
+        assert_eq!(game.current_player(), 1);
+
+        for _ in 1..SCORELESS_TURN_LIMIT {
+            assert!(!game.is_over());
+            game.pass()?;
+        }
+        assert!(game.is_over());
+        assert_eq!(game.pass().err(), Some(GameError::GameOver));
+
+        let scoresheet = game.scoresheet()?;
+        let penalties: i32 = scoresheet.adjustments.iter().map(|a| a.points).sum();
+        assert!(penalties < 0);
+        assert_eq!(scoresheet.final_scores.iter().sum::<i32>(), penalties);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_for_tiles_off_the_rack() -> Result<(), GameError> {
+        let mut game = Game::new(2, TileSet::english(), 1)?;
+        let missing = "abcdefghijklmnopqrstuvwxyz"
+            .chars()
+            .map(|letter| Tile {
+                letter,
+                blank: false,
+            })
+            .find(|&tile| !game.rack(0).unwrap().clone().remove(tile))
+            .unwrap();
+
+        let mv = Move::new(
+            Position::new(7, 7),
+            Direction::Across,
+            vec![missing, missing],
+        );
+        assert_eq!(game.play(mv).err(), Some(GameError::NotOnRack));
+        assert_eq!(game.current_player(), 0);
+        assert!(game.history().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_dictionary_errors() {
+        let parse = ParseError::BadLetter {
+            position: 2,
+            character: '1',
+        };
+        assert_eq!(
+            GameError::from(DictionaryError::Parse(parse.clone())),
+            GameError::Parse(parse)
+        );
+        assert_eq!(
+            GameError::from(DictionaryError::Io("missing".to_owned())),
+            GameError::Dictionary(DictionaryError::Io("missing".to_owned()))
+        );
+        assert_eq!(
+            GameError::from(DictionaryError::NotAWord("ZZ".to_owned())),
+            GameError::NotAWord("ZZ".to_owned())
+        );
+    }
+
+    #[test]
+    fn it_does_not_work_without_players() {
+        assert_eq!(
+            Game::new(0, TileSet::english(), 1).err(),
+            Some(GameError::NoPlayers)
+        );
+    }
+}
@@ -0,0 +1,801 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{
+    Board, Direction, Game, GameError, Move, MoveError, ParseError, Position, Rack, Tile, TileSet,
+    Turn,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum GcgError {
+    Io(String),
+    InvalidLine {
+        line_number: usize,
+        line: String,
+    },
+    UnknownPlayer {
+        line_number: usize,
+        nickname: String,
+    },
+    IllegalMove {
+        line_number: usize,
+        error: MoveError,
+    },
+    // The dots in a play don't line up with the tiles already on the board
+    WordMismatch {
+        line_number: usize,
+    },
+    // A tile left on a rack that the tile set has no value for
+    BadTile {
+        line_number: usize,
+        error: ParseError,
+    },
+    NothingToWithdraw {
+        line_number: usize,
+    },
+    WrongPlayerCount {
+        players: usize,
+        gcg_players: usize,
+    },
+    Game(GameError),
+}
+
+impl fmt::Display for GcgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GcgError::Io(e) => write!(f, "Could not read the game record: {}", e),
+            GcgError::InvalidLine { line_number, line } => {
+                write!(f, "Line {}: not a GCG event: {}", line_number, line)
+            }
+            GcgError::UnknownPlayer {
+                line_number,
+                nickname,
+            } => write!(f, "Line {}: no player is called {}", line_number, nickname),
+            GcgError::IllegalMove { line_number, error } => {
+                write!(f, "Line {}: {}", line_number, error)
+            }
+            GcgError::WordMismatch { line_number } => write!(
+                f,
+                "Line {}: the recorded word does not match the tiles on the board",
+                line_number
+            ),
+            GcgError::BadTile { line_number, error } => {
+                write!(f, "Line {}: {}", line_number, error)
+            }
+            GcgError::NothingToWithdraw { line_number } => {
+                write!(f, "Line {}: there is no play to withdraw", line_number)
+            }
+            GcgError::WrongPlayerCount {
+                players,
+                gcg_players,
+            } => write!(
+                f,
+                "There must be one GCG player per player, not {} for {}",
+                gcg_players, players
+            ),
+            GcgError::Game(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for GcgError {}
+
+impl From<io::Error> for GcgError {
+    fn from(e: io::Error) -> Self {
+        GcgError::Io(e.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GcgPlayer {
+    pub nickname: String,
+    pub name: String,
+}
+
+impl GcgPlayer {
+    pub fn new(nickname: &str, name: &str) -> Self {
+        GcgPlayer {
+            nickname: nickname.to_owned(),
+            name: name.to_owned(),
+        }
+    }
+}
+
+// A play's word runs from start, with None for the squares it plays through
+#[derive(Debug, PartialEq, Clone)]
+pub enum GcgEvent {
+    Play {
+        start: Position,
+        direction: Direction,
+        word: Vec<Option<Tile>>,
+    },
+    Exchange(Vec<Tile>),
+    Pass,
+    // The player's last play was a phony and came off the board
+    Withdrawn,
+    ChallengeBonus,
+    TimePenalty,
+    // Points for the tiles left on a rack at the end of the game
+    EndRack(Vec<Tile>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GcgLine {
+    pub line_number: usize,
+    pub player: usize,
+    pub rack: Rack,
+    pub event: GcgEvent,
+    pub points: i32,
+    pub total: i32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GcgGame {
+    pub players: Vec<GcgPlayer>,
+    pub lines: Vec<GcgLine>,
+    pub tile_set: TileSet,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScoreMismatch {
+    pub line_number: usize,
+    pub player: usize,
+    pub recorded: i32,
+    pub computed: i32,
+}
+
+impl fmt::Display for ScoreMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Line {}: recorded {:+} but scored {:+}",
+            self.line_number, self.recorded, self.computed
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GcgReplay {
+    pub board: Board,
+    pub scores: Vec<i32>,
+    pub mismatches: Vec<ScoreMismatch>,
+}
+
+// Rows are numbered from 1 and columns lettered from A. The row comes first
+// for a play across, so 8H is across from the center and H8 is down.
+fn parse_position(notation: &str) -> Option<(Position, Direction)> {
+    let first = notation.chars().next()?;
+    let (direction, col, row) = if first.is_ascii_alphabetic() {
+        (Direction::Down, first, &notation[1..])
+    } else {
+        let last = notation.chars().last()?;
+        let row = &notation[..notation.len() - last.len_utf8()];
+        (Direction::Across, last, row)
+    };
+    if !col.is_ascii_alphabetic() || !row.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let row = row.parse::<usize>().ok()?.checked_sub(1)?;
+    let col = (col.to_ascii_uppercase() as u8 - b'A') as usize;
+    let position = Position::new(row, col);
+    if position.is_on_board() {
+        Some((position, direction))
+    } else {
+        None
+    }
+}
+
+fn position_notation(start: Position, direction: Direction) -> String {
+    let col = (b'A' + start.col as u8) as char;
+    match direction {
+        Direction::Across => format!("{}{}", start.row + 1, col),
+        Direction::Down => format!("{}{}", col, start.row + 1),
+    }
+}
+
+// Upper case letters are tiles and lower case letters blanks, with . for
+// each tile already on the board
+fn parse_word(word: &str, tile_set: &TileSet) -> Option<Vec<Option<Tile>>> {
+    let mut squares = vec![];
+    let mut chars = word.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c == '.' {
+            chars.next();
+            squares.push(None);
+            continue;
+        }
+
+        let blank = c.is_lowercase();
+        let mut run = String::new();
+        while let Some(&c) = chars
+            .peek()
+            .filter(|&&c| c != '.' && c.is_lowercase() == blank)
+        {
+            run.push(c);
+            chars.next();
+        }
+        for letter in tile_set.tokenize(&run)? {
+            squares.push(Some(Tile { letter, blank }));
+        }
+    }
+
+    if squares.is_empty() {
+        None
+    } else {
+        Some(squares)
+    }
+}
+
+// Tiles and racks are written in upper case with ? for blanks
+fn tiles_notation(tiles: &[Tile], tile_set: &TileSet) -> String {
+    tiles
+        .iter()
+        .map(|tile| {
+            if tile.blank {
+                "?".to_owned()
+            } else {
+                tile.name(tile_set)
+            }
+        })
+        .collect()
+}
+
+fn same_tiles(a: &[Tile], b: &[Tile]) -> bool {
+    let sorted = |tiles: &[Tile]| {
+        let mut tiles = tiles.to_vec();
+        tiles.sort_by_key(|tile| (tile.letter, tile.blank));
+        tiles
+    };
+    sorted(a) == sorted(b)
+}
+
+// The move a recorded play makes, or None if the word's dots don't line up
+// with the tiles already on the board
+fn play_move(
+    board: &Board,
+    start: Position,
+    direction: Direction,
+    word: &[Option<Tile>],
+) -> Option<Move> {
+    let mut position = Some(start);
+    for square in word {
+        let p = position?;
+        if board.tile(p).is_some() != square.is_none() {
+            return None;
+        }
+        position = p.next(direction);
+    }
+
+    Some(Move::new(
+        start,
+        direction,
+        word.iter().flatten().cloned().collect(),
+    ))
+}
+
+impl GcgGame {
+    // Players come from the #player1, #player2, ... pragmas and each event
+    // from a ">nickname: RACK MOVE +SCORE TOTAL" line. Other pragmas, such as
+    // notes, are skipped.
+    pub fn parse(gcg: &str) -> Result<Self, GcgError> {
+        GcgGame::parse_with(gcg, TileSet::english())
+    }
+
+    pub fn parse_with(gcg: &str, tile_set: TileSet) -> Result<Self, GcgError> {
+        let mut players = vec![];
+        let mut lines = vec![];
+
+        for (i, line) in gcg.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            let invalid = || GcgError::InvalidLine {
+                line_number,
+                line: line.to_owned(),
+            };
+
+            if let Some(pragma) = line.strip_prefix("#player") {
+                let mut fields = pragma.splitn(3, ' ');
+                let number: usize = fields
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(invalid)?;
+                let nickname = fields
+                    .next()
+                    .filter(|n| !n.is_empty())
+                    .ok_or_else(invalid)?;
+                let name = fields.next().unwrap_or("").trim();
+                players.push((number, GcgPlayer::new(nickname, name)));
+                players.sort_by_key(|(number, _)| *number);
+                continue;
+            }
+            let event = match line.strip_prefix('>') {
+                Some(event) => event,
+                None if line.is_empty() || line.starts_with('#') => continue,
+                None => return Err(invalid()),
+            };
+
+            let (nickname, event) = event.split_once(':').ok_or_else(invalid)?;
+            let player = players
+                .iter()
+                .position(|(_, p)| p.nickname == nickname)
+                .ok_or_else(|| GcgError::UnknownPlayer {
+                    line_number,
+                    nickname: nickname.to_owned(),
+                })?;
+
+            let fields: Vec<&str> = event.split_whitespace().collect();
+            let (body, score) = match fields.len() {
+                n if n >= 3 => fields.split_at(n - 2),
+                _ => return Err(invalid()),
+            };
+            let points: i32 = score[0].parse().map_err(|_| invalid())?;
+            let total: i32 = score[1].parse().map_err(|_| invalid())?;
+
+            let play = match body {
+                [.., position, word] => parse_position(position).map(|p| (p, word)),
+                _ => None,
+            };
+            let (rack, event) = match (play, body.split_last()) {
+                (Some(((start, direction), word)), _) => (
+                    &body[..body.len() - 2],
+                    GcgEvent::Play {
+                        start,
+                        direction,
+                        word: parse_word(word, &tile_set).ok_or_else(invalid)?,
+                    },
+                ),
+                (None, Some((&last, rack))) => {
+                    let tiles = |tiles: &str| {
+                        Rack::from_string_with(tiles, &tile_set)
+                            .map(|rack| rack.tiles())
+                            .map_err(|_| invalid())
+                    };
+                    let event = match last {
+                        "-" => GcgEvent::Pass,
+                        "--" => GcgEvent::Withdrawn,
+                        "(challenge)" => GcgEvent::ChallengeBonus,
+                        "(time)" => GcgEvent::TimePenalty,
+                        _ if last.starts_with('-') => GcgEvent::Exchange(tiles(&last[1..])?),
+                        _ if last.starts_with('(') && last.ends_with(')') => {
+                            GcgEvent::EndRack(tiles(&last[1..last.len() - 1])?)
+                        }
+                        _ => return Err(invalid()),
+                    };
+                    (rack, event)
+                }
+                (None, None) => return Err(invalid()),
+            };
+            let rack = match rack {
+                [] => Rack::with_tile_set(tile_set.clone()),
+                [rack] => Rack::from_string_with(rack, &tile_set).map_err(|_| invalid())?,
+                _ => return Err(invalid()),
+            };
+
+            lines.push(GcgLine {
+                line_number,
+                player,
+                rack,
+                event,
+                points,
+                total,
+            });
+        }
+
+        Ok(GcgGame {
+            players: players.into_iter().map(|(_, player)| player).collect(),
+            lines,
+            tile_set,
+        })
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, GcgError> {
+        GcgGame::from_file_with(path, TileSet::english())
+    }
+
+    pub fn from_file_with<P: AsRef<Path>>(path: P, tile_set: TileSet) -> Result<Self, GcgError> {
+        GcgGame::parse_with(&fs::read_to_string(path)?, tile_set)
+    }
+
+    // The game's turns so far, with the end-game rack adjustments once it's
+    // over. A player who goes out gains the tiles left on every other rack
+    // and each of those players loses their own, as on the scoresheet.
+    pub fn from_game(game: &Game, players: Vec<GcgPlayer>) -> Result<Self, GcgError> {
+        let scoresheet = game.scoresheet().map_err(GcgError::Game)?;
+        if players.len() != scoresheet.final_scores.len() {
+            return Err(GcgError::WrongPlayerCount {
+                players: scoresheet.final_scores.len(),
+                gcg_players: players.len(),
+            });
+        }
+
+        let tile_set = game.board().tile_set().clone();
+        let mut board = Board::with_tile_set(tile_set.clone());
+        let mut lines = vec![];
+
+        for record in game.history() {
+            let entry = &record.entry;
+            let illegal = |error| GcgError::IllegalMove {
+                line_number: lines.len() + 1,
+                error,
+            };
+            let event = match &entry.turn {
+                Turn::Play(mv) => {
+                    // Writes out the main word, or the only word a single
+                    // tile makes
+                    let score = board.score_move(mv).map_err(illegal)?;
+                    let main_word = &score.words[0];
+                    let mut position = Some(main_word.start);
+                    let mut word = vec![];
+                    for &tile in &main_word.tiles {
+                        let p = position.ok_or_else(|| illegal(MoveError::RunsOffBoard))?;
+                        word.push(board.tile(p).map_or(Some(tile), |_| None));
+                        position = p.next(main_word.direction);
+                    }
+                    board.place(mv).map_err(illegal)?;
+
+                    GcgEvent::Play {
+                        start: main_word.start,
+                        direction: main_word.direction,
+                        word,
+                    }
+                }
+                Turn::Exchange(tiles) => GcgEvent::Exchange(tiles.clone()),
+                Turn::Pass => GcgEvent::Pass,
+            };
+
+            lines.push(GcgLine {
+                line_number: lines.len() + 1,
+                player: entry.player,
+                rack: record.rack.clone(),
+                event,
+                points: entry.points,
+                total: entry.total,
+            });
+        }
+
+        for adjustment in &scoresheet.adjustments {
+            let (rack, tiles) = if adjustment.rack.is_empty() {
+                let others = scoresheet
+                    .adjustments
+                    .iter()
+                    .flat_map(|a| a.rack.iter().cloned())
+                    .collect();
+                (Rack::with_tile_set(tile_set.clone()), others)
+            } else {
+                let mut rack = Rack::with_tile_set(tile_set.clone());
+                for &tile in &adjustment.rack {
+                    rack.add(tile);
+                }
+                (rack, adjustment.rack.clone())
+            };
+
+            lines.push(GcgLine {
+                line_number: lines.len() + 1,
+                player: adjustment.player,
+                rack,
+                event: GcgEvent::EndRack(tiles),
+                points: adjustment.points,
+                total: adjustment.total,
+            });
+        }
+
+        Ok(GcgGame {
+            players,
+            lines,
+            tile_set,
+        })
+    }
+
+    // Plays every event on a fresh board and scores it again. Challenge
+    // bonuses and time penalties can't be worked out from the board, so
+    // they're taken as recorded.
+    //
+    // A rack in brackets that matches the line's own rack is that player's
+    // penalty. Any other is the bonus for going out, which counts double
+    // unless the other players' penalties are recorded on their own lines.
+    pub fn replay(&self) -> Result<GcgReplay, GcgError> {
+        let mut board = Board::with_tile_set(self.tile_set.clone());
+        let mut plays: Vec<(Move, i32)> = vec![];
+        let mut scores = vec![0; self.players.len()];
+        let mut mismatches = vec![];
+
+        let is_penalty = |line: &GcgLine, tiles: &[Tile]| same_tiles(&line.rack.tiles(), tiles);
+        let penalties_recorded = self.lines.iter().any(|line| match &line.event {
+            GcgEvent::EndRack(tiles) => is_penalty(line, tiles),
+            _ => false,
+        });
+        let rack_value = |line: &GcgLine, tiles: &[Tile]| -> Result<i32, GcgError> {
+            Tile::tiles_value(tiles, &self.tile_set)
+                .map(|value| value as i32)
+                .map_err(|error| GcgError::BadTile {
+                    line_number: line.line_number,
+                    error,
+                })
+        };
+
+        for line in &self.lines {
+            let illegal = |error| GcgError::IllegalMove {
+                line_number: line.line_number,
+                error,
+            };
+
+            let computed = match &line.event {
+                GcgEvent::Play {
+                    start,
+                    direction,
+                    word,
+                } => {
+                    let mv = play_move(&board, *start, *direction, word).ok_or(
+                        GcgError::WordMismatch {
+                            line_number: line.line_number,
+                        },
+                    )?;
+                    let points = board.place(&mv).map_err(illegal)?.total as i32;
+                    plays.push((mv, points));
+                    points
+                }
+                GcgEvent::Exchange(_) | GcgEvent::Pass => 0,
+                GcgEvent::Withdrawn => {
+                    let (_, points) = plays.pop().ok_or(GcgError::NothingToWithdraw {
+                        line_number: line.line_number,
+                    })?;
+                    board = Board::with_tile_set(self.tile_set.clone());
+                    for (mv, _) in &plays {
+                        board.place(mv).map_err(illegal)?;
+                    }
+                    -points
+                }
+                GcgEvent::ChallengeBonus | GcgEvent::TimePenalty => line.points,
+                GcgEvent::EndRack(tiles) if is_penalty(line, tiles) => -rack_value(line, tiles)?,
+                GcgEvent::EndRack(tiles) if penalties_recorded => rack_value(line, tiles)?,
+                GcgEvent::EndRack(tiles) => 2 * rack_value(line, tiles)?,
+            };
+
+            scores[line.player] += computed;
+            if computed != line.points {
+                mismatches.push(ScoreMismatch {
+                    line_number: line.line_number,
+                    player: line.player,
+                    recorded: line.points,
+                    computed,
+                });
+            }
+        }
+
+        Ok(GcgReplay {
+            board,
+            scores,
+            mismatches,
+        })
+    }
+}
+
+impl fmt::Display for GcgGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "#character-encoding UTF-8")?;
+        for (i, player) in self.players.iter().enumerate() {
+            writeln!(f, "#player{} {} {}", i + 1, player.nickname, player.name)?;
+        }
+
+        for line in &self.lines {
+            // Lines can be edited to name a player the game doesn't have
+            let player = self.players.get(line.player).ok_or(fmt::Error)?;
+            let rack = tiles_notation(&line.rack.tiles(), &self.tile_set);
+            let event = match &line.event {
+                GcgEvent::Play {
+                    start,
+                    direction,
+                    word,
+                } => {
+                    let word: String = word
+                        .iter()
+                        .map(|square| square.map_or(".".to_owned(), |t| t.name(&self.tile_set)))
+                        .collect();
+                    format!("{} {}", position_notation(*start, *direction), word)
+                }
+                GcgEvent::Exchange(tiles) => format!("-{}", tiles_notation(tiles, &self.tile_set)),
+                GcgEvent::Pass => "-".to_owned(),
+                GcgEvent::Withdrawn => "--".to_owned(),
+                GcgEvent::ChallengeBonus => "(challenge)".to_owned(),
+                GcgEvent::TimePenalty => "(time)".to_owned(),
+                GcgEvent::EndRack(tiles) => format!("({})", tiles_notation(tiles, &self.tile_set)),
+            };
+
+            writeln!(
+                f,
+                ">{}: {} {} {:+} {}",
+                player.nickname, rack, event, line.points, line.total
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use crate::*;
+
+    const GAME: &str = "#character-encoding UTF-8
+#player1 alice Alice Smith
+#player2 bob Bob Jones
+>alice: AEHLLOT 8D HELLO +24 24
+>bob: ?DORSTZ H6 Zo. +11 11
+>alice: AEIQTUU -QUU +0 24
+>bob: DERSTX 8D .....X +16 27
+>bob: DERSTX -- -16 11
+#note ADZE was challenged
+>alice: ADEIOTT 6F AD.E +18 42
+>alice: IOTT (challenge) +5 47
+>bob: DERSTX - +0 11
+>bob: DERSTX (DERSTX) -14 -3
+>alice: IOTT (time) -10 37
+>alice: IOTT (IOTT) -4 33
+";
+
+    const WORD_LIST: &str = "aa ab ad ae ag ah ai al am an ar as at aw ax ay ba be bi bo by da de do ed ef eh el em en er es et ex fa fe go ha he hi hm ho id if in is it jo ka ki la li lo ma me mi mm mo mu my na ne no nu od oe of oh oi om on op or os ow ox oy pa pe pi qi re sh si so ta ti to uh um un up us ut we wo xi xu ya ye yo za";
+
+    #[test]
+    fn it_works_for_gcg_records() -> Result<(), GcgError> {
+        let game = GcgGame::parse(GAME)?;
+        assert_eq!(
+            game.players,
+            vec![
+                GcgPlayer::new("alice", "Alice Smith"),
+                GcgPlayer::new("bob", "Bob Jones")
+            ]
+        );
+        assert_eq!(game.lines.len(), 11);
+        assert_eq!(
+            game.lines[1].event,
+            GcgEvent::Play {
+                start: Position::new(5, 7),
+                direction: Direction::Down,
+                word: vec![
+                    Some(Tile {
+                        letter: 'z',
+                        blank: false
+                    }),
+                    Some(Tile {
+                        letter: 'o',
+                        blank: true
+                    }),
+                    None
+                ]
+            }
+        );
+        assert_eq!(game.lines[2].rack.to_string(), "AEIQTUU");
+        assert_eq!(game.lines[10].line_number, 15);
+
+        // ADZE was recorded as 18 but only makes 16
+        let replay = game.replay()?;
+        assert_eq!(
+            replay.mismatches,
+            vec![ScoreMismatch {
+                line_number: 10,
+                player: 0,
+                recorded: 18,
+                computed: 16
+            }]
+        );
+        assert_eq!(
+            replay.mismatches[0].to_string(),
+            "Line 10: recorded +18 but scored +16"
+        );
+        assert_eq!(replay.scores, vec![31, -3]);
+        // The phony X came off the board
+        assert_eq!(replay.board.tile(Position::new(7, 8)), None);
+
+        // Writing the record drops the note but keeps every event
+        let written = GcgGame::parse(&game.to_string())?;
+        assert_eq!(
+            written.lines.iter().map(|l| &l.event).collect::<Vec<_>>(),
+            game.lines.iter().map(|l| &l.event).collect::<Vec<_>>()
+        );
+        assert_eq!(written.replay()?.scores, replay.scores);
+
+        let mut edited = game.clone();
+        edited.lines[0].player = 2;
+        let mut out = String::new();
+        assert_eq!(write!(out, "{}", edited), Err(std::fmt::Error));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_going_out_bonuses() -> Result<(), GcgError> {
+        let game = GcgGame::parse(
+            "#player1 a\n#player2 b\n>a: HI 8G HI +10 10\n>b: ZO H7 O. +2 2\n>a:  (Z) +20 30\n",
+        )?;
+        let replay = game.replay()?;
+
+        assert!(replay.mismatches.is_empty());
+        assert_eq!(replay.scores, vec![30, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_games_written_from_the_engine() -> Result<(), GcgError> {
+        let dictionary = Dictionary::parse(&WORD_LIST.replace(' ', "\n")).unwrap();
+        let mut game = Game::new(2, TileSet::english(), 2019)
+            .unwrap()
+            .with_dictionary(dictionary.clone());
+        while !game.is_over() {
+            let rack = game.rack(game.current_player()).unwrap().clone();
+            let best = best_moves(Some(game.board()), &rack, &dictionary, 1);
+            match best.into_iter().next() {
+                Some(ranked) => game.play(ranked.mv).map(|_| ()),
+                None if game.bag().len() >= RACK_SIZE => game.exchange(rack.tiles()).map(|_| ()),
+                None => game.pass().map(|_| ()),
+            }
+            .unwrap();
+        }
+
+        let players = vec![
+            GcgPlayer::new("one", "Player One"),
+            GcgPlayer::new("two", ""),
+        ];
+        let gcg = GcgGame::from_game(&game, players.clone())?.to_string();
+        let replay = GcgGame::parse(&gcg)?.replay()?;
+
+        assert!(replay.mismatches.is_empty());
+        assert_eq!(replay.scores, game.scoresheet().unwrap().final_scores);
+        assert_eq!(&replay.board, game.board());
+        assert_eq!(
+            GcgGame::from_game(&game, players[..1].to_vec()),
+            Err(GcgError::WrongPlayerCount {
+                players: 2,
+                gcg_players: 1
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_for_bad_records() {
+        assert_eq!(
+            GcgGame::parse("#player1 alice\n>alice: AB 8H A@ +2 2"),
+            Err(GcgError::InvalidLine {
+                line_number: 2,
+                line: ">alice: AB 8H A@ +2 2".to_owned()
+            })
+        );
+        assert_eq!(
+            GcgGame::parse("#player1 alice\n>bob: AB 8H AB +8 8"),
+            Err(GcgError::UnknownPlayer {
+                line_number: 2,
+                nickname: "bob".to_owned()
+            })
+        );
+        assert_eq!(
+            GcgGame::parse("#player1 alice\n>alice: AB 8H AB +8 8\nnot a move"),
+            Err(GcgError::InvalidLine {
+                line_number: 3,
+                line: "not a move".to_owned()
+            })
+        );
+        assert_eq!(
+            GcgGame::parse("#player1 alice\n>alice: AB 1A AB +8 8").and_then(|g| g.replay()),
+            Err(GcgError::IllegalMove {
+                line_number: 2,
+                error: MoveError::NotOnCenter
+            })
+        );
+        assert_eq!(
+            GcgGame::parse("#player1 alice\n>alice: AB 8H .B +8 8").and_then(|g| g.replay()),
+            Err(GcgError::WordMismatch { line_number: 2 })
+        );
+        assert_eq!(
+            GcgGame::parse("#player1 alice\n>alice: AB -- +0 0").and_then(|g| g.replay()),
+            Err(GcgError::NothingToWithdraw { line_number: 2 })
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashSet};
+
+use crate::board::CENTER;
+use crate::dictionary::Node;
+use crate::{Board, Dictionary, Direction, Move, MoveScore, Position, Rack, Tile, BOARD_SIZE};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RankedMove {
+    pub mv: Move,
+    pub score: MoveScore,
+}
+
+struct Search<'a> {
+    board: &'a Board,
+    dictionary: &'a Dictionary,
+    direction: Direction,
+    seen: HashSet<Vec<(Position, Tile)>>,
+    moves: Vec<RankedMove>,
+}
+
+impl<'a> Search<'a> {
+    // The letters already on the board either side of position, across the
+    // direction of play
+    fn cross_word(&self, position: Position) -> (Vec<char>, Vec<char>) {
+        let across = self.direction.perpendicular();
+        let mut before = vec![];
+        let mut p = position.previous(across);
+        while let Some(tile) = p.and_then(|p| self.board.tile(p)) {
+            before.push(tile.letter);
+            p = p.and_then(|p| p.previous(across));
+        }
+
+        let mut after = vec![];
+        let mut p = position.next(across);
+        while let Some(tile) = p.and_then(|p| self.board.tile(p)) {
+            after.push(tile.letter);
+            p = p.and_then(|p| p.next(across));
+        }
+
+        before.reverse();
+        (before, after)
+    }
+
+    fn fits_cross_word(&self, position: Position, letter: char) -> bool {
+        let (mut word, after) = self.cross_word(position);
+        if word.is_empty() && after.is_empty() {
+            return true;
+        }
+
+        word.push(letter);
+        word.extend(after);
+        self.dictionary.contains_symbols(&word)
+    }
+
+    fn extend(
+        &mut self,
+        node: &Node,
+        start: Position,
+        position: Option<Position>,
+        length: usize,
+        placed: &mut Vec<(Position, Tile)>,
+        rack: &mut Rack,
+    ) {
+        if let Some(tile) = position.and_then(|p| self.board.tile(p)) {
+            if let Some(child) = node.children.get(&tile.letter) {
+                let next = position.and_then(|p| p.next(self.direction));
+                self.extend(child, start, next, length + 1, placed, rack);
+            }
+            return;
+        }
+
+        if node.is_word && length > 1 && !placed.is_empty() {
+            self.record(start, placed);
+        }
+
+        let position = match position {
+            Some(position) => position,
+            None => return,
+        };
+        let next = position.next(self.direction);
+
+        let letters: BTreeSet<char> = rack.letters.iter().cloned().collect();
+        for letter in letters {
+            let tile = Tile {
+                letter,
+                blank: false,
+            };
+            self.try_tile(node, start, position, next, length, tile, placed, rack);
+        }
+
+        if rack.blanks > 0 {
+            for &letter in node.children.keys() {
+                let tile = Tile {
+                    letter,
+                    blank: true,
+                };
+                self.try_tile(node, start, position, next, length, tile, placed, rack);
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn try_tile(
+        &mut self,
+        node: &Node,
+        start: Position,
+        position: Position,
+        next: Option<Position>,
+        length: usize,
+        tile: Tile,
+        placed: &mut Vec<(Position, Tile)>,
+        rack: &mut Rack,
+    ) {
+        let child = match node.children.get(&tile.letter) {
+            Some(child) if self.fits_cross_word(position, tile.letter) => child,
+            _ => return,
+        };
+
+        rack.remove(tile);
+        placed.push((position, tile));
+        self.extend(child, start, next, length + 1, placed, rack);
+        placed.pop();
+        rack.add(tile);
+    }
+
+    // Moves the board rejects, such as ones that don't connect, are dropped
+    // here. A single tile can be found once in each direction, so each set of
+    // placements is only kept once.
+    fn record(&mut self, start: Position, placed: &[(Position, Tile)]) {
+        let mut key = placed.to_vec();
+        key.sort_by_key(|(p, _)| (p.row, p.col));
+        if self.seen.contains(&key) {
+            return;
+        }
+
+        let tiles = placed.iter().map(|(_, tile)| *tile).collect();
+        let mv = Move::new(start, self.direction, tiles);
+        if let Ok(score) = self.board.score_move(&mv) {
+            self.seen.insert(key);
+            self.moves.push(RankedMove { mv, score });
+        }
+    }
+}
+
+// Every legal move for the rack, best first. Without a board the moves are
+// openings on an empty board.
+pub fn legal_moves(board: Option<&Board>, rack: &Rack, dictionary: &Dictionary) -> Vec<RankedMove> {
+    let empty_board = Board::with_tile_set(dictionary.tile_set().clone());
+    let board = board.unwrap_or(&empty_board);
+    let mut search = Search {
+        board,
+        dictionary,
+        direction: Direction::Across,
+        seen: HashSet::new(),
+        moves: vec![],
+    };
+
+    for &direction in &[Direction::Across, Direction::Down] {
+        search.direction = direction;
+
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                let start = Position::new(row, col);
+                let (line, offset) = match direction {
+                    Direction::Across => (row, col),
+                    Direction::Down => (col, row),
+                };
+
+                // Words start after an empty square, and an opening has to
+                // reach the center
+                let after_tile = start
+                    .previous(direction)
+                    .and_then(|p| board.tile(p))
+                    .is_some();
+                let misses_center = board.is_empty() && (line != CENTER.row || offset > CENTER.col);
+                if after_tile || misses_center {
+                    continue;
+                }
+
+                let mut rack = rack.clone();
+                search.extend(
+                    dictionary.root(),
+                    start,
+                    Some(start),
+                    0,
+                    &mut vec![],
+                    &mut rack,
+                );
+            }
+        }
+    }
+
+    let mut moves = search.moves;
+    moves.sort_by_key(|m| Reverse(m.score.total));
+    moves
+}
+
+pub fn best_moves(
+    board: Option<&Board>,
+    rack: &Rack,
+    dictionary: &Dictionary,
+    count: usize,
+) -> Vec<RankedMove> {
+    let mut moves = legal_moves(board, rack, dictionary);
+    moves.truncate(count);
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const WORD_LIST: &str = "ax
+ex
+ha
+he
+hello
+hellos
+lo
+ox
+qi
+so
+xi";
+
+    fn words(moves: &[RankedMove]) -> Vec<(String, u32)> {
+        moves
+            .iter()
+            .map(|m| (m.score.words[0].word.clone(), m.score.total))
+            .collect()
+    }
+
+    #[test]
+    fn it_works_for_an_opening_rack() -> Result<(), MoveError> {
+        let dictionary = Dictionary::parse(WORD_LIST).unwrap();
+        let moves = best_moves(None, &Rack::from_string("xoq")?, &dictionary, 3);
+
+        // OX fits either side of the center star in either direction
+        assert_eq!(
+            words(&moves),
+            vec![
+                ("OX".to_owned(), 18),
+                ("OX".to_owned(), 18),
+                ("OX".to_owned(), 18)
+            ]
+        );
+        assert!(moves
+            .iter()
+            .all(|m| m.score.words[0].start.row == 7 || m.score.words[0].start.col == 7));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_moves_through_and_across_tiles() -> Result<(), MoveError> {
+        let dictionary = Dictionary::parse(WORD_LIST).unwrap();
+        let mut board = Board::new();
+        board.place(&Move::new(
+            Position::new(7, 3),
+            Direction::Across,
+            Tile::tiles_from_string("hello")?,
+        ))?;
+
+        let moves = legal_moves(Some(&board), &Rack::from_string("sax")?, &dictionary);
+        assert!(!moves.is_empty());
+        for m in &moves {
+            assert_eq!(
+                board.score_move_with(&m.mv, &dictionary),
+                Ok(m.score.clone())
+            );
+        }
+        assert!(moves
+            .windows(2)
+            .all(|pair| pair[0].score.total >= pair[1].score.total));
+
+        let found = words(&moves);
+        assert!(found.contains(&("HELLOS".to_owned(), 9)));
+        // AX under HE forms HA and EX as well
+        assert!(found.contains(&("AX".to_owned(), 9 + 5 + 9)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_for_blank_tiles() -> Result<(), MoveError> {
+        let dictionary = Dictionary::parse(WORD_LIST).unwrap();
+        let moves = legal_moves(None, &Rack::from_string("q?")?, &dictionary);
+
+        // The blank can only make QI, scoring just the Q
+        assert!(!moves.is_empty());
+        assert!(moves
+            .iter()
+            .all(|m| m.score.words[0].word == "Qi" && m.score.total == 20));
+
+        assert!(legal_moves(None, &Rack::from_string("zz")?, &dictionary).is_empty());
+
+        Ok(())
+    }
+}
@@ -1,11 +1,32 @@
-type Error = &'static str;
-
 #[macro_use]
 extern crate lazy_static;
 
-use std::collections::HashMap;
-
-use regex::Regex;
+mod bag;
+mod board;
+mod dictionary;
+mod error;
+mod game;
+mod gcg;
+mod generator;
+mod rack;
+mod scoresheet;
+mod tile_set;
+
+pub use crate::bag::TileBag;
+pub use crate::board::{
+    Board, Direction, Move, MoveScore, Position, Premium, Tile, WordScore, BINGO_BONUS, BOARD_SIZE,
+    RACK_SIZE,
+};
+pub use crate::dictionary::{Dictionary, DictionaryError};
+pub use crate::error::{MoveError, ParseError};
+pub use crate::game::{Game, GameError, TurnRecord, SCORELESS_TURN_LIMIT};
+pub use crate::gcg::{GcgError, GcgEvent, GcgGame, GcgLine, GcgPlayer, GcgReplay, ScoreMismatch};
+pub use crate::generator::{best_moves, legal_moves, RankedMove};
+pub use crate::rack::Rack;
+pub use crate::scoresheet::{
+    EndGameAdjustment, Scoresheet, ScoresheetEntry, ScoresheetError, Turn,
+};
+pub use crate::tile_set::{TileKind, TileSet, TileSetError};
 
 enum ScoreModifier {
     Double,
@@ -21,115 +42,53 @@ impl ScoreModifier {
     }
 }
 
+// The position is where the letter starts in the text it came from, and is
+// only used to report a letter the tile set can't score
 struct Letter {
     c: char,
     score_modifier: Option<ScoreModifier>,
     blank_tile: bool,
+    position: usize,
 }
 
 impl Letter {
-    fn score(&self) -> u32 {
-        lazy_static! {
-            static ref LETTER_SCORES: HashMap<char, u32> = {
-                let mut m = HashMap::new();
-                m.insert('a', 1);
-                m.insert('b', 3);
-                m.insert('c', 3);
-                m.insert('d', 2);
-                m.insert('e', 1);
-                m.insert('f', 4);
-                m.insert('g', 2);
-                m.insert('h', 4);
-                m.insert('i', 1);
-                m.insert('j', 8);
-                m.insert('k', 5);
-                m.insert('l', 1);
-                m.insert('m', 3);
-                m.insert('n', 1);
-                m.insert('o', 1);
-                m.insert('p', 3);
-                m.insert('q', 10);
-                m.insert('r', 1);
-                m.insert('s', 1);
-                m.insert('t', 1);
-                m.insert('u', 1);
-                m.insert('v', 4);
-                m.insert('w', 4);
-                m.insert('x', 8);
-                m.insert('y', 4);
-                m.insert('z', 10);
-                m
-            };
-        }
-
+    fn score(&self, tile_set: &TileSet) -> Result<u32, ParseError> {
         if self.blank_tile {
-            0
-        } else {
-            let raw_score = LETTER_SCORES
-                .get(&self.c)
-                .expect(&format!("This is not a scorable letter: {}", self.c))
-                .clone();
-
-            match &self.score_modifier {
-                Some(m) => raw_score * m.multiplier(),
-                None => raw_score,
-            }
+            return Ok(0);
         }
-    }
-
-    fn letters_from_string(word: &str) -> Vec<Self> {
-        lazy_static! {
-            static ref LETTER_REGEX: Regex = Regex::new("([a-zA-Z])(\\*{0,2})(\\^?)").unwrap();
-        }
-
-        LETTER_REGEX
-            .captures_iter(word)
-            .map(|x| {
-                let letter = x.get(1).unwrap().as_str();
-                let multiplier = x.get(2).unwrap().as_str();
 
-                let score_modifier = ScoreModifier::from_letter_modifier_string(multiplier);
-
-                let blank_tile = x.get(3).unwrap().as_str() == "^";
-
-                Letter {
-                    c: letter.chars().next().unwrap(),
-                    score_modifier,
-                    blank_tile,
-                }
-            })
-            .collect()
+        let raw_score = tile_set.value(self.c).ok_or(ParseError::Unscorable {
+            position: self.position,
+            character: self.c,
+        })?;
+        Ok(match &self.score_modifier {
+            Some(m) => raw_score * m.multiplier(),
+            None => raw_score,
+        })
     }
 }
 
 impl ScoreModifier {
-    fn from_letter_modifier_string(s: &str) -> Option<Self> {
-        match s {
-            "" => None,
-            "*" => Some(ScoreModifier::Double),
-            "**" => Some(ScoreModifier::Triple),
-            _ => unreachable!("Unreachable due to regex parsing"),
+    fn from_letter_stars(stars: usize) -> Option<Self> {
+        match stars {
+            1 => Some(ScoreModifier::Double),
+            2 => Some(ScoreModifier::Triple),
+            _ => None,
         }
     }
+}
 
-    fn from_word_modifier_string(s: &str) -> Vec<Self> {
-        lazy_static! {
-            static ref REGEX: Regex = Regex::new("\\(([td])\\)").unwrap();
-        }
-        REGEX
-            .captures_iter(s)
-            .map(|x| {
-                let letter = x.get(1).unwrap();
-                println!("{:?}", x);
-
-                match letter.as_str() {
-                    "d" => ScoreModifier::Double,
-                    "t" => ScoreModifier::Triple,
-                    _ => panic!("Unreachable code block"),
-                }
-            })
-            .collect()
-    }
+// The characters of the trimmed text in lower case, and how many characters
+// were trimmed from the start so errors can point into the original text
+fn trimmed_lowercase(text: &str) -> (usize, Vec<char>) {
+    let leading = text.chars().take_while(|c| c.is_whitespace()).count();
+    let chars = text
+        .trim()
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+
+    (leading, chars)
 }
 
 struct Word {
@@ -138,37 +97,81 @@ struct Word {
 }
 
 impl Word {
-    fn from_string(word: &str) -> Result<Self, Error> {
-        lazy_static! {
-            static ref WORD_REGEX: Regex =
-                Regex::new("^((?:[a-z]\\*{0,2}\\^?)*)((?:\\([td]\\))*)$").unwrap();
-        }
-
-        let trimmed_word = word.trim().to_ascii_lowercase();
-
-        if WORD_REGEX.is_match(&trimmed_word) {
-            let capture = WORD_REGEX.captures(&trimmed_word).unwrap();
+    // Each letter may be followed by * or ** and then ^ for a blank, and the
+    // word may end with any number of (d) and (t). Error positions count
+    // characters from the start of the original string.
+    fn from_string(word: &str, tile_set: &TileSet) -> Result<Self, ParseError> {
+        let (leading, chars) = trimmed_lowercase(word);
+
+        let mut letters = vec![];
+        let mut modifiers = vec![];
+        let mut first_word_modifier = None;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let position = leading + i;
+            if let (Some(position), false) = (first_word_modifier, chars[i] == '(') {
+                return Err(ParseError::StrayWordModifier { position });
+            }
 
-            let letters = Letter::letters_from_string(capture.get(1).unwrap().as_str());
-            let word_modifier_capture = capture.get(2);
+            match chars[i] {
+                '(' => {
+                    let modifier = match (chars.get(i + 1), chars.get(i + 2)) {
+                        (Some('d'), Some(')')) => ScoreModifier::Double,
+                        (Some('t'), Some(')')) => ScoreModifier::Triple,
+                        _ => return Err(ParseError::BadWordModifier { position }),
+                    };
+                    first_word_modifier.get_or_insert(position);
+                    modifiers.push(modifier);
+                    i += 3;
+                }
+                '*' => return Err(ParseError::MisplacedMultiplier { position }),
+                '^' => return Err(ParseError::MisplacedBlank { position }),
+                character => {
+                    let rest: String = chars[i..].iter().collect();
+                    let (c, len) = tile_set.next_tile(&rest).ok_or(ParseError::BadLetter {
+                        position,
+                        character,
+                    })?;
+                    i += rest[..len].chars().count();
+
+                    let stars = chars[i..].iter().take_while(|&&c| c == '*').count();
+                    if stars > 2 {
+                        return Err(ParseError::TooManyMultipliers {
+                            position: leading + i + 2,
+                        });
+                    }
+                    i += stars;
+
+                    let blank_tile = chars.get(i) == Some(&'^');
+                    if blank_tile {
+                        i += 1;
+                    }
+
+                    letters.push(Letter {
+                        c,
+                        score_modifier: ScoreModifier::from_letter_stars(stars),
+                        blank_tile,
+                        position,
+                    });
+                }
+            }
+        }
 
-            let modifiers = match word_modifier_capture {
-                Some(c) => ScoreModifier::from_word_modifier_string(c.as_str()),
-                None => vec![],
-            };
+        Ok(Word { letters, modifiers })
+    }
 
-            Ok(Word { letters, modifiers })
-        } else {
-            Err("This is not a valid scrabble word")
-        }
+    fn score(&self, tile_set: &TileSet) -> Result<u32, ParseError> {
+        Ok(self.letters_score(tile_set)? * self.multiplier())
     }
 
-    fn score(&self) -> u32 {
-        self.letters_score() * self.multiplier()
+    // The tiles' symbols, with blanks as the letter they stand for
+    fn symbols(&self) -> Vec<char> {
+        self.letters.iter().map(|l| l.c).collect()
     }
 
-    fn letters_score(&self) -> u32 {
-        self.letters.iter().map(|l| l.score()).sum()
+    fn letters_score(&self, tile_set: &TileSet) -> Result<u32, ParseError> {
+        self.letters.iter().map(|l| l.score(tile_set)).sum()
     }
 
     fn multiplier(&self) -> u32 {
@@ -176,8 +179,13 @@ impl Word {
     }
 }
 
-pub fn scrabble_score(word: &str) -> Result<u32, Error> {
-    Ok(Word::from_string(word)?.score())
+pub fn scrabble_score(word: &str) -> Result<u32, ParseError> {
+    scrabble_score_with(word, &TileSet::english())
+}
+
+// Scores a word using another tile set's letters and values
+pub fn scrabble_score_with(word: &str, tile_set: &TileSet) -> Result<u32, ParseError> {
+    Word::from_string(word, tile_set)?.score(tile_set)
 }
 
 #[cfg(test)]
@@ -188,16 +196,22 @@ mod tests {
     fn it_does_not_work_with_invalid_chars() {
         assert_eq!(
             scrabble_score("t&@%&est"),
-            Err("This is not a valid scrabble word")
+            Err(ParseError::BadLetter {
+                position: 1,
+                character: '&'
+            })
         );
         assert_eq!(
             scrabble_score("123"),
-            Err("This is not a valid scrabble word")
+            Err(ParseError::BadLetter {
+                position: 0,
+                character: '1'
+            })
         );
     }
 
     #[test]
-    fn it_works_for_words_without_letter_modifiers() -> Result<(), Error> {
+    fn it_works_for_words_without_letter_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("test")?, 4);
         assert_eq!(scrabble_score("hello")?, 8);
         assert_eq!(scrabble_score("quiz")?, 22);
@@ -206,7 +220,7 @@ mod tests {
     }
 
     #[test]
-    fn it_works_for_mixed_case_words_without_modifiers() -> Result<(), Error> {
+    fn it_works_for_mixed_case_words_without_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("tESt")?, 4);
         assert_eq!(scrabble_score("heLLO")?, 8);
         assert_eq!(scrabble_score("quIz")?, 22);
@@ -215,7 +229,7 @@ mod tests {
     }
 
     #[test]
-    fn it_works_with_double_letter_modifiers() -> Result<(), Error> {
+    fn it_works_with_double_letter_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("t*e*s*t*")?, 8);
         assert_eq!(scrabble_score("h*ello")?, 12);
         assert_eq!(scrabble_score("q*u*iz")?, 33);
@@ -224,7 +238,7 @@ mod tests {
     }
 
     #[test]
-    fn it_works_with_blank_letter_modifiers() -> Result<(), Error> {
+    fn it_works_with_blank_letter_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("t**^e**s*t*")?, 7);
         assert_eq!(scrabble_score("h**^ello")?, 4);
         assert_eq!(scrabble_score("q*u*iz**^")?, 23);
@@ -233,7 +247,7 @@ mod tests {
     }
 
     #[test]
-    fn it_works_for_modified_words_without_letter_modifiers() -> Result<(), Error> {
+    fn it_works_for_modified_words_without_letter_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("test(t)")?, 12);
         assert_eq!(scrabble_score("hello(d)")?, 16);
         assert_eq!(scrabble_score("quiz(d)(d)")?, 88);
@@ -242,7 +256,7 @@ mod tests {
     }
 
     #[test]
-    fn it_works_with_triple_letter_modifiers() -> Result<(), Error> {
+    fn it_works_with_triple_letter_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("t**e**s*t*")?, 10);
         assert_eq!(scrabble_score("h**ello")?, 16);
         assert_eq!(scrabble_score("q*u*iz**")?, 53);
@@ -251,11 +265,88 @@ mod tests {
     }
 
     #[test]
-    fn it_works_with_mixed_word_and_letter_modifiers() -> Result<(), Error> {
+    fn it_works_with_mixed_word_and_letter_modifiers() -> Result<(), ParseError> {
         assert_eq!(scrabble_score("t**e**s*t*(d)")?, 20);
         assert_eq!(scrabble_score("h**ello(d)(d)")?, 64);
         assert_eq!(scrabble_score("q*u*iz**^(t)")?, 69);
 
         Ok(())
     }
+
+    #[test]
+    fn it_works_with_other_tile_sets() -> Result<(), ParseError> {
+        assert_eq!(scrabble_score_with("chorro", &TileSet::spanish())?, 15);
+        assert_eq!(
+            scrabble_score_with("CH**orr^o(d)", &TileSet::spanish())?,
+            34
+        );
+        assert_eq!(scrabble_score_with("ñu", &TileSet::spanish())?, 9);
+        assert_eq!(scrabble_score_with("Bär", &TileSet::german())?, 10);
+        assert_eq!(scrabble_score_with("kiwi", &TileSet::french())?, 22);
+        assert_eq!(
+            scrabble_score_with("ñu", &TileSet::english()),
+            Err(ParseError::BadLetter {
+                position: 0,
+                character: 'ñ'
+            })
+        );
+        // Positions count characters as for any other error, not letters
+        assert_eq!(
+            Word::from_string(" qu*ñ^a", &TileSet::spanish())?.score(&TileSet::english()),
+            Ok(13)
+        );
+        assert_eq!(
+            Word::from_string(" qu*ña", &TileSet::spanish())?.score(&TileSet::english()),
+            Err(ParseError::Unscorable {
+                position: 4,
+                character: 'ñ'
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_with_misplaced_modifiers() {
+        assert_eq!(
+            scrabble_score("te***st"),
+            Err(ParseError::TooManyMultipliers { position: 4 })
+        );
+        assert_eq!(
+            scrabble_score("  *test"),
+            Err(ParseError::MisplacedMultiplier { position: 2 })
+        );
+        assert_eq!(
+            scrabble_score("t^^est"),
+            Err(ParseError::MisplacedBlank { position: 2 })
+        );
+        assert_eq!(
+            scrabble_score("te^*st"),
+            Err(ParseError::MisplacedMultiplier { position: 3 })
+        );
+        assert_eq!(
+            scrabble_score("te(d)st"),
+            Err(ParseError::StrayWordModifier { position: 2 })
+        );
+        assert_eq!(
+            scrabble_score("test(q)"),
+            Err(ParseError::BadWordModifier { position: 4 })
+        );
+        assert_eq!(
+            scrabble_score("test(d"),
+            Err(ParseError::BadWordModifier { position: 4 })
+        );
+        assert_eq!(
+            ParseError::TooManyMultipliers { position: 4 }.to_string(),
+            "Too many letter multipliers at position 4"
+        );
+    }
+
+    #[test]
+    fn it_works_for_empty_and_padded_words() -> Result<(), ParseError> {
+        assert_eq!(scrabble_score("")?, 0);
+        assert_eq!(scrabble_score("  quiz(d)  ")?, 44);
+
+        Ok(())
+    }
 }
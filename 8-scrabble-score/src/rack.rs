@@ -0,0 +1,187 @@
+use std::fmt;
+
+use crate::{trimmed_lowercase, ParseError, Tile, TileSet};
+
+// The tiles a player holds. Blanks don't stand for a letter until they are
+// played. Displaying a rack spells its tiles with the tile set's names, so
+// Spanish racks show CH, LL and RR.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct Rack {
+    pub letters: Vec<char>,
+    pub blanks: usize,
+    tile_set: TileSet,
+}
+
+impl Rack {
+    pub fn new() -> Self {
+        Rack::default()
+    }
+
+    pub fn with_tile_set(tile_set: TileSet) -> Self {
+        Rack {
+            tile_set,
+            ..Rack::default()
+        }
+    }
+
+    pub fn tile_set(&self) -> &TileSet {
+        &self.tile_set
+    }
+
+    // Letters with ? for each blank, so "retains?" or "QI??". Error positions
+    // count characters from the start of the original string.
+    pub fn from_string(rack: &str) -> Result<Self, ParseError> {
+        Rack::from_string_with(rack, &TileSet::english())
+    }
+
+    pub fn from_string_with(rack: &str, tile_set: &TileSet) -> Result<Self, ParseError> {
+        let (leading, chars) = trimmed_lowercase(rack);
+        let mut result = Rack::with_tile_set(tile_set.clone());
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] == '?' {
+                result.blanks += 1;
+                i += 1;
+                continue;
+            }
+
+            let rest: String = chars[i..].iter().collect();
+            let (letter, len) = tile_set.next_tile(&rest).ok_or(ParseError::BadLetter {
+                position: leading + i,
+                character: chars[i],
+            })?;
+            i += rest[..len].chars().count();
+            result.letters.push(letter);
+        }
+
+        Ok(result)
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.len() + self.blanks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn add(&mut self, tile: Tile) {
+        if tile.blank {
+            self.blanks += 1;
+        } else {
+            self.letters.push(tile.letter);
+        }
+    }
+
+    // Takes the tile off the rack, returning false if it isn't there. Any
+    // blank can be played as a blank tile, whatever letter it stands for.
+    pub fn remove(&mut self, tile: Tile) -> bool {
+        if tile.blank {
+            if self.blanks == 0 {
+                return false;
+            }
+            self.blanks -= 1;
+            return true;
+        }
+
+        match self.letters.iter().position(|&c| c == tile.letter) {
+            Some(i) => {
+                self.letters.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // The tiles left at the end of a game, with blanks as '?'
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.letters
+            .iter()
+            .map(|&letter| Tile {
+                letter,
+                blank: false,
+            })
+            .chain((0..self.blanks).map(|_| Tile {
+                letter: '?',
+                blank: true,
+            }))
+            .collect()
+    }
+}
+
+impl fmt::Display for Rack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &letter in &self.letters {
+            write!(f, "{}", self.tile_set.name(letter).to_uppercase())?;
+        }
+        for _ in 0..self.blanks {
+            write!(f, "?")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn it_works_for_racks() -> Result<(), ParseError> {
+        let mut rack = Rack::from_string("Qi?a")?;
+        assert_eq!(rack.len(), 4);
+        assert_eq!(rack.to_string(), "QIA?");
+
+        assert!(rack.remove(Tile {
+            letter: 'i',
+            blank: false
+        }));
+        assert!(rack.remove(Tile {
+            letter: 'z',
+            blank: true
+        }));
+        assert!(!rack.remove(Tile {
+            letter: 'z',
+            blank: true
+        }));
+        assert!(!rack.remove(Tile {
+            letter: 'e',
+            blank: false
+        }));
+        assert_eq!(rack.to_string(), "QA");
+
+        rack.add(Tile {
+            letter: 'x',
+            blank: true,
+        });
+        assert_eq!(Tile::tiles_value(&rack.tiles(), &TileSet::english())?, 11);
+        assert_eq!(
+            Rack::from_string("ab1"),
+            Err(ParseError::BadLetter {
+                position: 2,
+                character: '1'
+            })
+        );
+        assert_eq!(Rack::from_string_with("ñ?", &TileSet::spanish())?.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shows_racks_with_their_tile_set() -> Result<(), ParseError> {
+        let mut rack = Rack::from_string_with("chñ?llrra", &TileSet::spanish())?;
+        assert_eq!(rack.len(), 6);
+        assert_eq!(rack.to_string(), "CHÑLLRRA?");
+        assert!(rack.remove(Tile::tiles_from_string_with("ll", &TileSet::spanish())?[0]));
+        assert_eq!(rack.to_string(), "CHÑRRA?");
+
+        assert_eq!(
+            Rack::from_string_with("äö", &TileSet::german())?.to_string(),
+            "ÄÖ"
+        );
+        assert_eq!(Rack::with_tile_set(TileSet::german()).to_string(), "");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt;
+
+use crate::{Board, Move, MoveError, MoveScore, ParseError, Tile, TileSet};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScoresheetError {
+    NoPlayers,
+    WrongRackCount { players: usize, racks: usize },
+    // Turns count from 1
+    IllegalMove { turn: usize, error: MoveError },
+    BadTile(ParseError),
+}
+
+impl fmt::Display for ScoresheetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScoresheetError::NoPlayers => write!(f, "A game needs at least one player"),
+            ScoresheetError::WrongRackCount { players, racks } => write!(
+                f,
+                "There must be one final rack per player, not {} for {}",
+                racks, players
+            ),
+            ScoresheetError::IllegalMove { turn, error } => write!(f, "Turn {}: {}", turn, error),
+            ScoresheetError::BadTile(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScoresheetError {}
+
+impl From<ParseError> for ScoresheetError {
+    fn from(e: ParseError) -> Self {
+        ScoresheetError::BadTile(e)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Turn {
+    Play(Move),
+    Exchange(Vec<Tile>),
+    Pass,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScoresheetEntry {
+    pub player: usize,
+    pub turn: Turn,
+    pub score: Option<MoveScore>,
+    pub points: i32,
+    pub total: i32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EndGameAdjustment {
+    pub player: usize,
+    pub rack: Vec<Tile>,
+    pub points: i32,
+    pub total: i32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Scoresheet {
+    pub entries: Vec<ScoresheetEntry>,
+    pub adjustments: Vec<EndGameAdjustment>,
+    pub final_scores: Vec<i32>,
+    pub board: Board,
+}
+
+impl Scoresheet {
+    // Players take the turns in order, starting with player 0. At the end each
+    // player loses the value of the tiles left on their rack, and a player who
+    // went out gains the value of everyone else's.
+    pub fn new(
+        players: usize,
+        turns: &[Turn],
+        final_racks: &[Vec<Tile>],
+    ) -> Result<Self, ScoresheetError> {
+        Scoresheet::with_tile_set(TileSet::english(), players, turns, final_racks)
+    }
+
+    pub fn with_tile_set(
+        tile_set: TileSet,
+        players: usize,
+        turns: &[Turn],
+        final_racks: &[Vec<Tile>],
+    ) -> Result<Self, ScoresheetError> {
+        if players == 0 {
+            return Err(ScoresheetError::NoPlayers);
+        }
+        if final_racks.len() != players {
+            return Err(ScoresheetError::WrongRackCount {
+                players,
+                racks: final_racks.len(),
+            });
+        }
+
+        let mut board = Board::with_tile_set(tile_set);
+        let mut scores = vec![0; players];
+        let mut entries = vec![];
+
+        for (i, turn) in turns.iter().enumerate() {
+            let player = i % players;
+            let score = match turn {
+                Turn::Play(mv) => Some(
+                    board
+                        .place(mv)
+                        .map_err(|error| ScoresheetError::IllegalMove { turn: i + 1, error })?,
+                ),
+                Turn::Exchange(_) | Turn::Pass => None,
+            };
+            let points = score.as_ref().map_or(0, |s| s.total as i32);
+            scores[player] += points;
+
+            entries.push(ScoresheetEntry {
+                player,
+                turn: turn.clone(),
+                score,
+                points,
+                total: scores[player],
+            });
+        }
+
+        let went_out = final_racks.iter().any(|rack| rack.is_empty());
+        let rack_values = final_racks
+            .iter()
+            .map(|rack| Ok(Tile::tiles_value(rack, board.tile_set())? as i32))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
+        let left_on_racks: i32 = rack_values.iter().sum();
+        let adjustments = final_racks
+            .iter()
+            .zip(&rack_values)
+            .enumerate()
+            .map(|(player, (rack, &value))| {
+                let points = if rack.is_empty() && went_out {
+                    left_on_racks
+                } else {
+                    -value
+                };
+                scores[player] += points;
+
+                EndGameAdjustment {
+                    player,
+                    rack: rack.clone(),
+                    points,
+                    total: scores[player],
+                }
+            })
+            .collect();
+
+        Ok(Scoresheet {
+            entries,
+            adjustments,
+            final_scores: scores,
+            board,
+        })
+    }
+
+    // Every player tied on the highest final score
+    pub fn winners(&self) -> Vec<usize> {
+        let best = self.final_scores.iter().max();
+
+        (0..self.final_scores.len())
+            .filter(|&player| self.final_scores.get(player) == best)
+            .collect()
+    }
+}
+
+fn tiles_string(tiles: &[Tile], tile_set: &TileSet) -> String {
+    tiles
+        .iter()
+        .map(|tile| {
+            if tile.blank {
+                "?".to_owned()
+            } else {
+                tile.name(tile_set)
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Scoresheet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            let description = match (&entry.turn, &entry.score) {
+                (Turn::Play(_), Some(score)) => {
+                    let words: Vec<&str> = score.words.iter().map(|w| w.word.as_str()).collect();
+                    let bingo = if score.bingo { " (bingo)" } else { "" };
+                    format!("{}{}", words.join(", "), bingo)
+                }
+                (Turn::Exchange(tiles), _) => {
+                    format!("exchange {}", tiles_string(tiles, self.board.tile_set()))
+                }
+                _ => "pass".to_owned(),
+            };
+
+            writeln!(
+                f,
+                "Player {}: {} {} ({})",
+                entry.player + 1,
+                description,
+                entry.points,
+                entry.total
+            )?;
+        }
+
+        for adjustment in &self.adjustments {
+            let description = if adjustment.rack.is_empty() {
+                "went out".to_owned()
+            } else {
+                format!(
+                    "rack {}",
+                    tiles_string(&adjustment.rack, self.board.tile_set())
+                )
+            };
+
+            writeln!(
+                f,
+                "Player {}: {} {} ({})",
+                adjustment.player + 1,
+                description,
+                adjustment.points,
+                adjustment.total
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn play(row: usize, col: usize, direction: Direction, tiles: &str) -> Result<Turn, ParseError> {
+        Ok(Turn::Play(Move::new(
+            Position::new(row, col),
+            direction,
+            Tile::tiles_from_string(tiles)?,
+        )))
+    }
+
+    #[test]
+    fn it_works_for_a_player_going_out() -> Result<(), ScoresheetError> {
+        let turns = vec![
+            play(7, 1, Direction::Across, "letters")?,
+            Turn::Pass,
+            play(8, 1, Direction::Across, "ax")?,
+        ];
+        let racks = vec![vec![], Tile::tiles_from_string("qi")?];
+        let scoresheet = Scoresheet::new(2, &turns, &racks)?;
+
+        let points: Vec<(usize, i32, i32)> = scoresheet
+            .entries
+            .iter()
+            .map(|e| (e.player, e.points, e.total))
+            .collect();
+        // AX under LE also makes LA and EX, with the X on a double letter
+        assert_eq!(points, vec![(0, 66, 66), (1, 0, 0), (0, 17 + 2 + 17, 102)]);
+        assert_eq!(scoresheet.final_scores, vec![102 + 11, -11]);
+        assert_eq!(scoresheet.winners(), vec![0]);
+        assert_eq!(
+            scoresheet.to_string(),
+            "Player 1: LETTERS (bingo) 66 (66)
+Player 2: pass 0 (0)
+Player 1: AX, LA, EX 36 (102)
+Player 1: went out 11 (113)
+Player 2: rack QI -11 (-11)
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_works_when_nobody_goes_out() -> Result<(), ScoresheetError> {
+        let turns = vec![
+            play(7, 6, Direction::Across, "ox")?,
+            Turn::Exchange(Tile::tiles_from_string("vv")?),
+        ];
+        let racks = vec![
+            Tile::tiles_from_string("e^a")?,
+            Tile::tiles_from_string("z")?,
+        ];
+        let scoresheet = Scoresheet::new(2, &turns, &racks)?;
+
+        assert_eq!(scoresheet.final_scores, vec![18 - 1, -10]);
+        assert_eq!(
+            scoresheet.board.tile(Position::new(7, 7)).map(|t| t.letter),
+            Some('x')
+        );
+        assert!(scoresheet
+            .to_string()
+            .contains("Player 2: exchange VV 0 (0)\n"));
+        assert!(scoresheet
+            .to_string()
+            .contains("Player 1: rack ?A -1 (17)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_without_a_rack_per_player() -> Result<(), ParseError> {
+        assert_eq!(
+            Scoresheet::new(2, &[], &[vec![]]),
+            Err(ScoresheetError::WrongRackCount {
+                players: 2,
+                racks: 1
+            })
+        );
+        assert_eq!(
+            Scoresheet::new(0, &[], &[]),
+            Err(ScoresheetError::NoPlayers)
+        );
+        let off_center = Turn::Play(Move::new(
+            Position::new(0, 0),
+            Direction::Across,
+            Tile::tiles_from_string("hi")?,
+        ));
+        assert_eq!(
+            Scoresheet::new(1, &[Turn::Pass, off_center], &[vec![]]),
+            Err(ScoresheetError::IllegalMove {
+                turn: 2,
+                error: MoveError::NotOnCenter
+            })
+        );
+        let spanish_rack = Tile::tiles_from_string_with("añ", &TileSet::spanish())?;
+        assert_eq!(
+            Scoresheet::new(1, &[], &[spanish_rack]),
+            Err(ScoresheetError::BadTile(ParseError::Unscorable {
+                position: 1,
+                character: 'ñ'
+            }))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// One tile per line as "name value count", with ? naming the blanks
+const ENGLISH: &str = "a 1 9
+b 3 2
+c 3 2
+d 2 4
+e 1 12
+f 4 2
+g 2 3
+h 4 2
+i 1 9
+j 8 1
+k 5 1
+l 1 4
+m 3 2
+n 1 6
+o 1 8
+p 3 2
+q 10 1
+r 1 6
+s 1 4
+t 1 6
+u 1 4
+v 4 2
+w 4 2
+x 8 1
+y 4 2
+z 10 1
+? 0 2";
+
+const FRENCH: &str = "a 1 9
+b 3 2
+c 3 2
+d 2 3
+e 1 15
+f 4 2
+g 2 2
+h 4 2
+i 1 8
+j 8 1
+k 10 1
+l 1 5
+m 2 3
+n 1 6
+o 1 6
+p 3 2
+q 8 1
+r 1 6
+s 1 6
+t 1 6
+u 1 6
+v 4 2
+w 10 1
+x 10 1
+y 10 1
+z 10 1
+? 0 2";
+
+const SPANISH: &str = "a 1 12
+b 3 2
+c 3 4
+ch 5 1
+d 2 5
+e 1 12
+f 4 1
+g 2 2
+h 4 2
+i 1 6
+j 8 1
+l 1 4
+ll 8 1
+m 3 2
+n 1 5
+ñ 8 1
+o 1 9
+p 3 2
+q 5 1
+r 1 5
+rr 8 1
+s 1 6
+t 1 4
+u 1 5
+v 4 1
+x 8 1
+y 4 1
+z 10 1
+? 0 2";
+
+const GERMAN: &str = "a 1 5
+ä 6 1
+b 3 2
+c 4 2
+d 1 4
+e 1 15
+f 4 2
+g 2 3
+h 2 4
+i 1 6
+j 6 1
+k 4 2
+l 2 3
+m 3 4
+n 1 9
+o 2 3
+ö 8 1
+p 4 1
+q 10 1
+r 1 6
+s 1 7
+t 1 6
+u 1 6
+ü 6 1
+v 6 1
+w 3 1
+x 8 1
+y 10 1
+z 3 1
+? 0 2";
+
+// Tiles named by more than one letter are given symbols from the private use
+// area, so every tile is still a single char on the board and in the dictionary
+const FIRST_MULTI_LETTER_SYMBOL: u32 = 0xE000;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TileSetError {
+    Io(String),
+    InvalidLine { line_number: usize, line: String },
+}
+
+impl fmt::Display for TileSetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TileSetError::Io(e) => write!(f, "Could not read the tile set: {}", e),
+            TileSetError::InvalidLine { line_number, line } => {
+                write!(
+                    f,
+                    "Line {}: expected a tile, value and count: {}",
+                    line_number, line
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TileSetError {}
+
+impl From<io::Error> for TileSetError {
+    fn from(e: io::Error) -> Self {
+        TileSetError::Io(e.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TileKind {
+    pub name: String,
+    pub symbol: char,
+    pub value: u32,
+    pub count: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TileSet {
+    pub tiles: Vec<TileKind>,
+    pub blanks: u32,
+}
+
+impl Default for TileSet {
+    fn default() -> Self {
+        TileSet::english()
+    }
+}
+
+impl TileSet {
+    pub fn english() -> Self {
+        lazy_static! {
+            static ref TILE_SET: TileSet = TileSet::parse(ENGLISH).unwrap();
+        }
+        TILE_SET.clone()
+    }
+
+    pub fn french() -> Self {
+        lazy_static! {
+            static ref TILE_SET: TileSet = TileSet::parse(FRENCH).unwrap();
+        }
+        TILE_SET.clone()
+    }
+
+    pub fn spanish() -> Self {
+        lazy_static! {
+            static ref TILE_SET: TileSet = TileSet::parse(SPANISH).unwrap();
+        }
+        TILE_SET.clone()
+    }
+
+    pub fn german() -> Self {
+        lazy_static! {
+            static ref TILE_SET: TileSet = TileSet::parse(GERMAN).unwrap();
+        }
+        TILE_SET.clone()
+    }
+
+    // Blank lines and lines starting with # are skipped
+    pub fn parse(tile_set: &str) -> Result<Self, TileSetError> {
+        let mut result = TileSet {
+            tiles: vec![],
+            blanks: 0,
+        };
+        let mut next_symbol = FIRST_MULTI_LETTER_SYMBOL;
+
+        for (i, line) in tile_set.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || TileSetError::InvalidLine {
+                line_number: i + 1,
+                line: line.to_owned(),
+            };
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (name, value, count) = match fields.as_slice() {
+                [name, value, count] => (
+                    name.to_lowercase(),
+                    value.parse().map_err(|_| invalid())?,
+                    count.parse().map_err(|_| invalid())?,
+                ),
+                _ => return Err(invalid()),
+            };
+
+            if name == "?" {
+                result.blanks = count;
+                continue;
+            }
+            if !name.chars().all(char::is_alphabetic) || result.find(&name).is_some() {
+                return Err(invalid());
+            }
+
+            let mut letters = name.chars();
+            let symbol = match (letters.next(), letters.next()) {
+                (Some(letter), None) => letter,
+                _ => {
+                    next_symbol += 1;
+                    std::char::from_u32(next_symbol - 1).ok_or_else(invalid)?
+                }
+            };
+            result.tiles.push(TileKind {
+                name,
+                symbol,
+                value,
+                count,
+            });
+        }
+
+        Ok(result)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, TileSetError> {
+        TileSet::parse(&fs::read_to_string(path)?)
+    }
+
+    fn find(&self, name: &str) -> Option<&TileKind> {
+        self.tiles.iter().find(|kind| kind.name == name)
+    }
+
+    fn kind(&self, symbol: char) -> Option<&TileKind> {
+        self.tiles.iter().find(|kind| kind.symbol == symbol)
+    }
+
+    pub fn value(&self, symbol: char) -> Option<u32> {
+        self.kind(symbol).map(|kind| kind.value)
+    }
+
+    pub fn count(&self, symbol: char) -> Option<u32> {
+        self.kind(symbol).map(|kind| kind.count)
+    }
+
+    pub fn total_tiles(&self) -> u32 {
+        self.tiles.iter().map(|kind| kind.count).sum::<u32>() + self.blanks
+    }
+
+    // The tile at the start of the lower case text and how many bytes it
+    // takes up. The longest name wins, so Spanish "chorro" starts with CH.
+    pub(crate) fn next_tile(&self, text: &str) -> Option<(char, usize)> {
+        self.tiles
+            .iter()
+            .filter(|kind| text.starts_with(kind.name.as_str()))
+            .max_by_key(|kind| kind.name.len())
+            .map(|kind| (kind.symbol, kind.name.len()))
+    }
+
+    // Splits a word into tile symbols, or None if some part isn't a tile
+    pub fn tokenize(&self, word: &str) -> Option<Vec<char>> {
+        let word = word.to_lowercase();
+        let mut rest = word.as_str();
+        let mut symbols = vec![];
+        while !rest.is_empty() {
+            let (symbol, len) = self.next_tile(rest)?;
+            symbols.push(symbol);
+            rest = &rest[len..];
+        }
+
+        Some(symbols)
+    }
+
+    pub fn name(&self, symbol: char) -> String {
+        self.kind(symbol)
+            .map_or_else(|| symbol.to_string(), |kind| kind.name.clone())
+    }
+
+    pub fn spell(&self, symbols: &[char]) -> String {
+        symbols.iter().map(|&symbol| self.name(symbol)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use crate::*;
+
+    #[test]
+    fn it_works_for_the_built_in_sets() {
+        let english = TileSet::english();
+        assert_eq!(english.total_tiles(), 100);
+        assert_eq!(english.value('q'), Some(10));
+        assert_eq!(english.count('e'), Some(12));
+        assert_eq!(english.blanks, 2);
+
+        assert_eq!(TileSet::french().total_tiles(), 102);
+        assert_eq!(TileSet::french().value('k'), Some(10));
+        assert_eq!(TileSet::spanish().total_tiles(), 100);
+        assert_eq!(TileSet::german().total_tiles(), 102);
+        assert_eq!(TileSet::german().value('ö'), Some(8));
+        assert_eq!(TileSet::default(), english);
+    }
+
+    #[test]
+    fn it_works_for_multi_letter_tiles() {
+        let spanish = TileSet::spanish();
+        let symbols = spanish.tokenize("Chorro").unwrap();
+
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(spanish.name(symbols[0]), "ch");
+        assert_eq!(spanish.value(symbols[2]), Some(8));
+        assert_eq!(spanish.spell(&symbols), "chorro");
+        assert_eq!(spanish.tokenize("niño").map(|s| s.len()), Some(4));
+        assert_eq!(TileSet::english().tokenize("niño"), None);
+    }
+
+    #[test]
+    fn it_works_for_custom_tile_set_files() -> Result<(), TileSetError> {
+        let path = env::temp_dir().join("scrabble-score-tile-set-test.txt");
+        fs::write(&path, "# Two tiles and a blank\na 1 3\nTH 6 1\n\n? 0 1\n").unwrap();
+        let tile_set = TileSet::from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let tile_set = tile_set?;
+        assert_eq!(tile_set.total_tiles(), 5);
+        assert_eq!(tile_set.tokenize("that").map(|s| s.len()), None);
+        assert_eq!(tile_set.tokenize("tha").map(|s| s.len()), Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_does_not_work_for_bad_tile_set_lines() {
+        assert_eq!(
+            TileSet::parse("a 1 9\nb three 2"),
+            Err(TileSetError::InvalidLine {
+                line_number: 2,
+                line: "b three 2".to_owned()
+            })
+        );
+        assert_eq!(
+            TileSet::parse("a 1 9\na 1 2"),
+            Err(TileSetError::InvalidLine {
+                line_number: 2,
+                line: "a 1 2".to_owned()
+            })
+        );
+        assert!(TileSet::parse("a-b 1 1").is_err());
+    }
+}